@@ -1,26 +1,18 @@
+use utils::parse::{FromLine, ParseResult};
 use utils::*;
 
+#[derive(FromLine)]
 enum Turn {
+    #[line("L{}")]
     Left(usize),
+    #[line("R{}")]
     Right(usize),
 }
 
-impl Turn {
-    fn from_str(s: &str) -> Option<Self> {
-        let (dir, dist) = s.split_at(1);
-        let distance = dist.parse().ok()?;
-        match dir {
-            "L" => Some(Turn::Left(distance)),
-            "R" => Some(Turn::Right(distance)),
-            _ => None,
-        }
-    }
-}
-
-fn part1(input: &str) -> usize {
+fn part1(input: &str) -> ParseResult<usize> {
     let mut zeros = 0;
     let mut value = 50;
-    for turn in input.lines().map(|line| Turn::from_str(line).unwrap()) {
+    for turn in parse::lines(input, Turn::parse_line)? {
         match turn {
             Turn::Left(d) => value = (value + 1000 - d) % 100,
             Turn::Right(d) => value = (value + d) % 100,
@@ -29,13 +21,13 @@ fn part1(input: &str) -> usize {
             zeros += 1;
         }
     }
-    zeros
+    Ok(zeros)
 }
 
-fn part2(input: &str) -> usize {
+fn part2(input: &str) -> ParseResult<usize> {
     let mut wraps = 0;
     let mut value = 50;
-    for turn in input.lines().map(|line| Turn::from_str(line).unwrap()) {
+    for turn in parse::lines(input, Turn::parse_line)? {
         match turn {
             Turn::Left(d) => {
                 let mut d = d;
@@ -61,7 +53,7 @@ fn part2(input: &str) -> usize {
             }
         }
     }
-    wraps
+    Ok(wraps)
 }
 
 fn main() {
@@ -1,4 +1,5 @@
 use itertools::Itertools;
+use utils::parse::{Delimiter, ParseResult, Parser};
 use utils::*;
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
@@ -31,41 +32,30 @@ struct Machine {
 }
 
 impl Machine {
-    fn from_str(line: &str) -> Self {
+    fn parse(p: &mut Parser) -> ParseResult<Self> {
         let mut goal = None;
         let mut buttons = Vec::new();
         let mut joltage = None;
-        for entry in line.split_whitespace() {
-            if entry.starts_with('[') {
-                let states: Vec<SwitchState> = entry
-                    .trim_matches(&['[', ']'][..])
-                    .chars()
-                    .map(SwitchState::from_char)
-                    .collect();
-                goal = Some(states);
-            } else if entry.starts_with('(') {
-                let button: Vec<usize> = entry
-                    .trim_matches(&['(', ')'][..])
-                    .split(',')
-                    .map(|s| s.parse().unwrap())
-                    .collect();
-                buttons.push(button);
-            } else if entry.starts_with('{') {
-                let jolts: Vec<usize> = entry
-                    .trim_matches(&['{', '}'][..])
-                    .split(',')
-                    .map(|s| s.parse().unwrap())
-                    .collect();
-                joltage = Some(jolts);
+        for group in p.groups()? {
+            match group.delimiter {
+                Delimiter::Square => {
+                    let mut states = group.parser();
+                    let mut switches = vec![];
+                    while !states.is_empty() {
+                        switches.push(SwitchState::from_char(states.one_of(&['#', '.'])?));
+                    }
+                    goal = Some(switches);
+                }
+                Delimiter::Round => buttons.push(group.list(",")?),
+                Delimiter::Curly => joltage = Some(group.list(",")?),
+                Delimiter::Angle => return Err(group.error("a [...], (...) or {...} group")),
             }
         }
-        let goal = goal.unwrap();
-        let joltage = joltage.unwrap();
-        Self {
-            goal,
+        Ok(Self {
+            goal: goal.ok_or_else(|| p.error("a [...] group"))?,
             buttons,
-            joltage,
-        }
+            joltage: joltage.ok_or_else(|| p.error("a {...} group"))?,
+        })
     }
 
     fn min_num_clicks(&self) -> usize {
@@ -110,20 +100,18 @@ impl Machine {
     }
 }
 
-fn part1(input: &str) -> usize {
-    input
-        .lines()
-        .map(Machine::from_str)
-        .map(|machine| machine.min_num_clicks())
-        .sum()
+fn part1(input: &str) -> ParseResult<usize> {
+    Ok(parse::lines(input, Machine::parse)?
+        .iter()
+        .map(Machine::min_num_clicks)
+        .sum())
 }
 
-fn part2(input: &str) -> usize {
-    input
-        .lines()
-        .map(Machine::from_str)
-        .map(|machine| machine.min_num_clicks_part2())
-        .sum()
+fn part2(input: &str) -> ParseResult<usize> {
+    Ok(parse::lines(input, Machine::parse)?
+        .iter()
+        .map(Machine::min_num_clicks_part2)
+        .sum())
 }
 
 fn main() {
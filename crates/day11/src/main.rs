@@ -1,62 +1,55 @@
-use std::collections::HashMap;
-
+use utils::graph::{EdgeMode, Graph};
+use utils::intern::Id;
+use utils::parse::ParseResult;
 use utils::*;
 
-fn part1(input: &str) -> usize {
-    let mut paths_map = input
-        .lines()
-        .map(|line| line.split_once(": ").unwrap())
-        .map(|(key, line)| (key, line.split_whitespace().collect::<Vec<_>>()))
-        .collect::<HashMap<_, _>>();
-    paths_map.insert("out", vec![]);
-    pathfinding::prelude::count_paths(
-        "you",
-        |state| paths_map.get(state).unwrap().iter().map(|&next| next),
-        |state| *state == "out",
-    )
+fn part1(input: &str) -> ParseResult<usize> {
+    let graph = Graph::parse(input, EdgeMode::Directed)?;
+    let you = graph.require_node("you")?;
+    let out = graph.require_node("out")?;
+    Ok(pathfinding::prelude::count_paths(
+        you,
+        |&state| graph.neighbors(state),
+        |&state| state == out,
+    ))
 }
 
 #[derive(PartialEq, Eq, Hash, Clone)]
-struct PathState<'a> {
-    curr: &'a str,
+struct PathState {
+    curr: Id,
     has_visited_dac: bool,
     has_visited_fft: bool,
 }
 
-fn part2(input: &str) -> usize {
-    let mut paths_map = input
-        .lines()
-        .map(|line| line.split_once(": ").unwrap())
-        .map(|(key, line)| (key, line.split_whitespace().collect::<Vec<_>>()))
-        .collect::<HashMap<_, _>>();
-    paths_map.insert("out", vec![]);
-    pathfinding::prelude::count_paths(
+fn part2(input: &str) -> ParseResult<usize> {
+    let graph = Graph::parse(input, EdgeMode::Directed)?;
+    let svr = graph.require_node("svr")?;
+    let out = graph.require_node("out")?;
+    let dac = graph.require_node("dac")?;
+    let fft = graph.require_node("fft")?;
+    Ok(pathfinding::prelude::count_paths(
         PathState {
-            curr: "svr",
+            curr: svr,
             has_visited_dac: false,
             has_visited_fft: false,
         },
         |state| {
             let has_visited_dac = state.has_visited_dac;
             let has_visited_fft = state.has_visited_fft;
-            paths_map
-                .get(state.curr)
-                .unwrap()
-                .iter()
-                .map(move |&next| PathState {
-                    curr: next,
-                    has_visited_dac: has_visited_dac || next == "dac",
-                    has_visited_fft: has_visited_fft || next == "fft",
-                })
+            graph.neighbors(state.curr).map(move |next| PathState {
+                curr: next,
+                has_visited_dac: has_visited_dac || next == dac,
+                has_visited_fft: has_visited_fft || next == fft,
+            })
         },
-        |state| state.curr == "out" && state.has_visited_dac && state.has_visited_fft,
-    )
+        |state| state.curr == out && state.has_visited_dac && state.has_visited_fft,
+    ))
 }
 
 fn main() {
     part1_test!(5);
     part1_answer!(607);
-    test_part2(|| part2(include_str!("./input_test2.txt")), 2, &"day11");
+    test_part2(|| part2(include_str!("./input_test2.txt")), 2, "day11");
     part2_answer!(506264456238938);
 }
 
@@ -1,22 +1,27 @@
+use utils::grid::Grid;
+use utils::parse::{ParseResult, scan};
 use utils::*;
 
-fn part1(input: &str) -> usize {
-    input
-        .lines()
-        .filter(|line| line.contains('x'))
-        .map(|line| {
-            let (dims, quantities) = line.split_once(": ").unwrap();
-            let mut dims = dims.split('x');
-            let width = dims.next().unwrap().parse::<usize>().unwrap();
-            let height = dims.next().unwrap().parse::<usize>().unwrap();
-            let shape_quantities = whitespaced_ints(quantities);
-            (width, height, shape_quantities)
-        })
+fn part1(input: &str) -> ParseResult<usize> {
+    let (_, regions) = Grid::labelled_blocks(input, |c| c == '#')?;
+    let Some(regions) = regions else {
+        return Ok(0);
+    };
+    let regions = regions.map_lines(|line| {
+        let (width, height, quantities): (usize, usize, &str) = scan!(line, "{}x{}: {..}")?;
+        Ok((
+            width,
+            height,
+            ints(quantities).collect::<ParseResult<Vec<usize>>>()?,
+        ))
+    })?;
+    Ok(regions
+        .iter()
         .filter(|(width, height, shape_quantities)| {
             // Hmm...
             (width / 3) * (height / 3) >= shape_quantities.iter().sum()
         })
-        .count()
+        .count())
 }
 
 fn main() {
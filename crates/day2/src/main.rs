@@ -1,13 +1,16 @@
+use utils::parse::{FromLine, ParseResult, Parser};
 use utils::*;
 
+#[derive(FromLine)]
+#[line("{}-{}")]
 struct Id(usize, usize);
 
 impl Id {
-    fn from_str(s: &str) -> Self {
-        let mut parts = s.split('-');
-        let first = parts.next().unwrap().parse().unwrap();
-        let second = parts.next().unwrap().parse().unwrap();
-        Id(first, second)
+    fn parse_all(input: &str) -> ParseResult<Vec<Self>> {
+        let mut p = Parser::new(input, 1);
+        let ids = p.list(",", Id::parse_line)?;
+        p.end()?;
+        Ok(ids)
     }
 
     fn invalid_sum_part1(&self) -> usize {
@@ -47,18 +50,18 @@ impl Id {
     }
 }
 
-fn part1(input: &str) -> usize {
-    input
-        .split(",")
-        .map(|s| Id::from_str(s).invalid_sum_part1())
-        .sum()
+fn part1(input: &str) -> ParseResult<usize> {
+    Ok(Id::parse_all(input)?
+        .iter()
+        .map(Id::invalid_sum_part1)
+        .sum())
 }
 
-fn part2(input: &str) -> usize {
-    input
-        .split(",")
-        .map(|s| Id::from_str(s).invalid_sum_part2())
-        .sum()
+fn part2(input: &str) -> ParseResult<usize> {
+    Ok(Id::parse_all(input)?
+        .iter()
+        .map(Id::invalid_sum_part2)
+        .sum())
 }
 
 fn main() {
@@ -1,60 +1,59 @@
-use utils::grid::Grid;
+use utils::direction::OrdinalDirection;
+use utils::grid::automaton::Automaton;
+use utils::grid::{BitGrid, Grid, GridCell, GridError};
 use utils::*;
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, GridCell)]
 enum Cell {
+    #[cell('.')]
     Empty,
+    #[cell('@')]
     Paper,
 }
 
-impl Cell {
-    fn from_char(c: char) -> Self {
-        match c {
-            '.' => Cell::Empty,
-            '@' => Cell::Paper,
-            _ => panic!("Invalid character for Cell"),
-        }
-    }
+fn part1(input: &str) -> Result<usize, GridError> {
+    let grid = Grid::<Cell>::parse(input)?;
+    let paper = BitGrid::from_grid(&grid, |&c| c == Cell::Paper);
+    let crowded = BitGrid::at_least(&paper.neighbor_masks::<OrdinalDirection>(), 4);
+    Ok(paper.and_not(&crowded).count_ones())
 }
 
-fn part1(input: &str) -> usize {
-    let grid = Grid::from_str(input, Cell::from_char);
-    grid.iter()
-        .filter(|(pos, c)| {
-            **c == Cell::Paper
-                && grid
-                    .neighbors_ordinal(*pos)
-                    .iter()
-                    .filter(|neighbor| *grid.get(**neighbor).unwrap() == Cell::Paper)
-                    .count()
-                    < 4
-        })
-        .count()
+fn part2(input: &str) -> Result<usize, GridError> {
+    let mut grid = Grid::<Cell>::parse(input)?;
+    let report = Automaton::<OrdinalDirection>::new().worklist(&mut grid, |&cell, neighbors| {
+        if cell == Cell::Paper && neighbors.count(&Cell::Paper) < 4 {
+            Cell::Empty
+        } else {
+            cell
+        }
+    });
+    Ok(report.changed)
 }
 
-fn part2(input: &str) -> usize {
-    let mut removed = 0;
-    let mut grid = Grid::from_str(input, Cell::from_char);
-    while let Some((accessible_paper_pos, _)) = grid.iter().find(|(pos, c)| {
-        **c == Cell::Paper
-            && grid
-                .neighbors_ordinal(*pos)
-                .iter()
-                .filter(|neighbor| *grid.get(**neighbor).unwrap() == Cell::Paper)
-                .count()
-                < 4
-    }) {
-        grid[accessible_paper_pos] = Cell::Empty;
-        removed += 1;
-    }
-    removed
+/// A random grid of exactly `cells` cells, as close to square as `cells` allows.
+fn generate(cells: usize) -> String {
+    let width = (1..=cells.isqrt())
+        .rev()
+        .find(|&width| cells.is_multiple_of(width))
+        .unwrap_or(1);
+    let mut rng = scaling::Rng::new(cells as u64);
+    (0..cells / width)
+        .map(|_| {
+            (0..width)
+                .map(|_| if rng.chance(2, 3) { '@' } else { '.' })
+                .collect::<String>()
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
 }
 
 fn main() {
-    part1_test!(13);
-    part1_answer!(1424);
-    part2_test!(43);
-    part2_answer!(8727);
+    part1_test!(grid, 13);
+    part1_answer!(grid, 1424);
+    part2_test!(grid, 43);
+    part2_answer!(grid, 8727);
+    part1_scaling!(generate, [625, 1250, 2500, 5000, 10000]);
+    part2_scaling!(generate, [625, 1250, 2500, 5000, 10000]);
 }
 
 #[test]
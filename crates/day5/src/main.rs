@@ -1,37 +1,39 @@
+use utils::parse::FromLine;
+use utils::stream::{InputStream, StreamError};
 use utils::*;
 
-fn part1(input: &str) -> usize {
-    let (ranges, ingredients) = utils::split_double_newline_once(input);
-    let ranges = ranges
-        .lines()
-        .map(|line| line.split_once("-").unwrap())
-        .map(|range| (range.0.parse().unwrap(), range.1.parse().unwrap()))
-        .collect::<Vec<_>>();
-    ingredients
-        .lines()
-        .map(|line| line.parse::<usize>().unwrap())
-        .filter(|ingredient| {
-            ranges
+#[derive(FromLine)]
+#[line("{}-{}")]
+struct Range(usize, usize);
+
+fn read_ranges(stream: &mut InputStream) -> Result<Vec<(usize, usize)>, StreamError> {
+    stream
+        .section_values::<Range>()
+        .map(|range| range.map(|Range(start, end)| (start, end)))
+        .collect()
+}
+
+fn part1_stream(stream: &mut InputStream) -> Result<usize, StreamError> {
+    let ranges = read_ranges(stream)?;
+    stream
+        .section_values::<usize>()
+        .try_fold(0, |fresh, ingredient| {
+            let ingredient = ingredient?;
+            let is_fresh = ranges
                 .iter()
-                .any(|(start, end)| ingredient >= start && ingredient <= end)
+                .any(|&(start, end)| ingredient >= start && ingredient <= end);
+            Ok(fresh + usize::from(is_fresh))
         })
-        .count()
 }
 
-fn part2(input: &str) -> usize {
-    let mut ranges = utils::split_double_newline_once(input)
-        .0
-        .lines()
-        .map(|line| line.split_once("-").unwrap())
-        .map(|range| {
-            (
-                range.0.parse::<usize>().unwrap(),
-                range.1.parse::<usize>().unwrap(),
-            )
-        })
-        .collect::<Vec<_>>();
+fn part1(input: &str) -> Result<usize, StreamError> {
+    part1_stream(&mut InputStream::from_text(input))
+}
+
+fn part2_stream(stream: &mut InputStream) -> Result<usize, StreamError> {
+    let mut ranges = read_ranges(stream)?;
     ranges.sort();
-    ranges
+    Ok(ranges
         .iter()
         .fold(Vec::<(usize, usize)>::new(), |mut list, curr| {
             for range in list.iter_mut() {
@@ -49,7 +51,11 @@ fn part2(input: &str) -> usize {
         })
         .iter()
         .map(|v| v.1 - v.0 + 1)
-        .sum()
+        .sum())
+}
+
+fn part2(input: &str) -> Result<usize, StreamError> {
+    part2_stream(&mut InputStream::from_text(input))
 }
 
 fn main() {
@@ -57,6 +63,8 @@ fn main() {
     part1_answer!(896);
     part2_test!(14);
     part2_answer!(346240317247002);
+    part1_stream!();
+    part2_stream!();
 }
 
 #[test]
@@ -1,3 +1,5 @@
+use utils::grid::Grid;
+use utils::parse::ParseResult;
 use utils::*;
 
 #[derive(Debug, Clone, Copy)]
@@ -14,6 +16,14 @@ impl Operator {
             _ => panic!("Unknown operator: {}", s),
         }
     }
+
+    fn from_char(c: char) -> Option<Self> {
+        match c {
+            '+' => Some(Operator::Add),
+            '*' => Some(Operator::Mul),
+            _ => None,
+        }
+    }
 }
 
 fn part1(input: &str) -> usize {
@@ -21,14 +31,9 @@ fn part1(input: &str) -> usize {
         .lines()
         .map(|line| line.split_whitespace().collect::<Vec<_>>())
         .collect::<Vec<_>>();
-    let mut rotated = vec![vec![]; list[0].len()];
-    for row in list {
-        for (i, val) in row.iter().enumerate() {
-            rotated[i].push(*val);
-        }
-    }
-    rotated
-        .iter()
+    Grid::from_2d_vec(list)
+        .transpose()
+        .iter_rows()
         .map(|exp| {
             let op = Operator::from_str(exp.iter().last().unwrap());
             let it = exp
@@ -43,52 +48,21 @@ fn part1(input: &str) -> usize {
         .sum()
 }
 
-fn part2(input: &str) -> usize {
-    let list = input
-        .lines()
-        .map(|line| line.chars().collect::<Vec<_>>())
-        .collect::<Vec<_>>();
-    let mut rotated = vec![vec![]; list[0].len()];
-    for row in input.lines() {
-        for (i, val) in row.chars().enumerate() {
-            rotated[i].push(val);
-        }
-    }
-    let mut grouped = vec![];
-    let mut curr = vec![];
-    let mut curr_op = Operator::Add;
-    for line in rotated {
-        match line.iter().last() {
-            Some('+') => {
-                curr_op = Operator::Add;
-            }
-            Some('*') => {
-                curr_op = Operator::Mul;
-            }
-            _ => {}
-        }
-        if line.iter().collect::<String>().trim() == "" {
-            grouped.push((curr_op, curr));
-            curr = vec![];
-        } else {
-            curr.push(
-                line.iter()
-                    .filter(|c| c.is_numeric())
-                    .map(|c| *c)
-                    .collect::<String>()
-                    .parse::<usize>()
-                    .unwrap(),
-            );
-        }
-    }
-    if curr.len() > 0 {
-        grouped.push((curr_op, curr));
-    }
-    grouped
+fn part2(input: &str) -> ParseResult<usize> {
+    Grid::char_grid(input)
+        .split_cols(|&c| c == ' ')
         .iter()
-        .map(|(op, nums)| match op {
-            Operator::Add => nums.iter().sum::<usize>(),
-            Operator::Mul => nums.iter().product(),
+        .map(|block| {
+            let op = block
+                .grid
+                .iter()
+                .find_map(|(_, &c)| Operator::from_char(c))
+                .unwrap();
+            let nums = block.grid.col_numbers::<usize>()?;
+            Ok(match op {
+                Operator::Add => nums.iter().sum::<usize>(),
+                Operator::Mul => nums.iter().product(),
+            })
         })
         .sum()
 }
@@ -96,8 +70,8 @@ fn part2(input: &str) -> usize {
 fn main() {
     part1_test!(4277556);
     part1_answer!(6100348226985);
-    part2_test!(3263827);
-    part2_answer!(12377473011151);
+    part2_test!(untrimmed, 3263827);
+    part2_answer!(untrimmed, 12377473011151);
 }
 
 #[test]
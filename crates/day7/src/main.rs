@@ -1,33 +1,30 @@
 use std::collections::{HashMap, HashSet};
 
-use utils::{grid::Position, *};
+use utils::grid::{Grid, GridCell, GridError, Position};
+use utils::*;
 
-#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, GridCell)]
 enum Cell {
+    #[cell('.')]
     Empty,
+    #[cell('S')]
     BeamStart,
+    #[cell('^')]
     Splitter,
 }
 
-impl Cell {
-    fn from_char(c: char) -> Self {
-        match c {
-            'S' => Cell::BeamStart,
-            '^' => Cell::Splitter,
-            _ => Cell::Empty,
-        }
-    }
-}
-
-fn part1(input: &str) -> usize {
-    let grid = grid::Grid::from_str(input, Cell::from_char);
-    let start_pos = grid.find(|cell| *cell == Cell::BeamStart).unwrap();
+fn part1(input: &str) -> Result<usize, GridError> {
+    let grid = Grid::<Cell>::parse(input)?;
+    let start_pos = grid.find_cell(Cell::BeamStart)?;
     let mut current_beam_positions = HashSet::new();
     let mut next_beam_positions = HashSet::new();
     let mut all_beam_positions = HashSet::<Position>::new();
     current_beam_positions.insert(start_pos);
     let mut splits = 0;
-    while current_beam_positions.len() > 0 {
+    let mut depth = 0;
+    while !current_beam_positions.is_empty() {
+        let _layer =
+            tracing::debug_span!("layer", depth, beams = current_beam_positions.len()).entered();
         for pos in current_beam_positions.drain() {
             if let Some(cell) = grid.get(pos) {
                 match cell {
@@ -54,17 +51,19 @@ fn part1(input: &str) -> usize {
         }
         all_beam_positions.extend(next_beam_positions.iter());
         std::mem::swap(&mut current_beam_positions, &mut next_beam_positions);
+        tracing::trace!(splits, "layer done");
+        depth += 1;
     }
-    splits
+    Ok(splits)
 }
 
-pub fn part2(input: &str) -> usize {
-    let grid = grid::Grid::from_str(input, Cell::from_char);
+pub fn part2(input: &str) -> Result<usize, GridError> {
+    let grid = Grid::<Cell>::parse(input)?;
     let mut map = HashMap::<Position, usize>::default();
-    let start_pos = grid.find(|cell| *cell == Cell::BeamStart).unwrap();
+    let start_pos = grid.find_cell(Cell::BeamStart)?;
     map.insert(start_pos, 1);
     for (pos, cell) in grid.iter().filter(|(pos, _)| pos.0 > 0) {
-        if let Some(num_paths) = map.get(&(pos.0 - 1, pos.1)).map(|x| *x) {
+        if let Some(num_paths) = map.get(&(pos.0 - 1, pos.1)).copied() {
             match cell {
                 Cell::Empty => {
                     *map.entry(pos).or_default() += num_paths;
@@ -78,16 +77,17 @@ pub fn part2(input: &str) -> usize {
         }
     }
 
-    map.iter()
+    Ok(map
+        .iter()
         .filter_map(|(pos, num_paths)| (pos.0 == grid.height() - 1).then_some(num_paths))
-        .sum()
+        .sum())
 }
 
 fn main() {
-    part1_test!(21);
-    part1_answer!(1579);
-    part2_test!(40);
-    part2_answer!(13418215871354);
+    part1_test!(grid, 21);
+    part1_answer!(grid, 1579);
+    part2_test!(grid, 40);
+    part2_answer!(grid, 13418215871354);
 }
 
 #[test]
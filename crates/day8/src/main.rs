@@ -1,9 +1,11 @@
 use std::collections::{HashMap, HashSet};
 
 use itertools::Itertools;
+use utils::parse::{FromLine, ParseResult};
 use utils::*;
 
-#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq, PartialOrd, Ord, FromLine)]
+#[line("{x},{y},{z}")]
 struct Point {
     x: usize,
     y: usize,
@@ -11,15 +13,6 @@ struct Point {
 }
 
 impl Point {
-    fn from_str(s: &str) -> Self {
-        let mut coords = s.split(',');
-        Point {
-            x: coords.next().unwrap().parse().unwrap(),
-            y: coords.next().unwrap().parse().unwrap(),
-            z: coords.next().unwrap().parse().unwrap(),
-        }
-    }
-
     fn straight_line_distance(&self, other: &Point) -> f64 {
         let dx = (self.x as isize - other.x as isize) as f64;
         let dy = (self.y as isize - other.y as isize) as f64;
@@ -28,10 +21,9 @@ impl Point {
     }
 }
 
-fn part1(input: &str, max: usize) -> usize {
-    let mut mappings = input
-        .lines()
-        .map(Point::from_str)
+fn part1(input: &str, max: usize) -> ParseResult<usize> {
+    let mut mappings = parse::lines(input, Point::parse_line)?
+        .into_iter()
         .tuple_combinations()
         .fold(HashMap::new(), |mut acc, (p1, p2)| {
             acc.insert((p1.min(p2), p1.max(p2)), p1.straight_line_distance(&p2));
@@ -52,29 +44,29 @@ fn part1(input: &str, max: usize) -> usize {
                 sets[j.min(i)].extend(set_b);
             }
             (Some(i), None) => {
-                sets[i].insert(b.clone());
+                sets[i].insert(*b);
             }
             (None, Some(j)) => {
-                sets[j].insert(a.clone());
+                sets[j].insert(*a);
             }
             (None, None) => {
                 let mut new_set = HashSet::new();
-                new_set.insert(a.clone());
-                new_set.insert(b.clone());
+                new_set.insert(*a);
+                new_set.insert(*b);
                 sets.push(new_set);
             }
             _ => {}
         }
     }
-    sets.sort_by(|a, b| b.len().cmp(&a.len()));
-    sets.iter().take(3).map(|set| set.len()).product()
+    sets.sort_by_key(|set| std::cmp::Reverse(set.len()));
+    Ok(sets.iter().take(3).map(|set| set.len()).product())
 }
 
-fn part2(input: &str) -> usize {
-    let num_points = input.lines().count();
-    let mut mappings = input
-        .lines()
-        .map(Point::from_str)
+fn part2(input: &str) -> ParseResult<usize> {
+    let points = parse::lines(input, Point::parse_line)?;
+    let num_points = points.len();
+    let mut mappings = points
+        .into_iter()
         .tuple_combinations()
         .fold(HashMap::new(), |mut acc, (p1, p2)| {
             acc.insert((p1.min(p2), p1.max(p2)), p1.straight_line_distance(&p2));
@@ -96,25 +88,35 @@ fn part2(input: &str) -> usize {
                 sets[j.min(i)].extend(set_b);
             }
             (Some(i), None) => {
-                sets[i].insert(b.clone());
+                sets[i].insert(*b);
             }
             (None, Some(j)) => {
-                sets[j].insert(a.clone());
+                sets[j].insert(*a);
             }
             (None, None) => {
                 let mut new_set = HashSet::new();
-                new_set.insert(a.clone());
-                new_set.insert(b.clone());
+                new_set.insert(*a);
+                new_set.insert(*b);
                 sets.push(new_set);
             }
             _ => {}
         };
         if sets.len() == 1 && sets[0].len() == num_points {
-            last_join = Some((a.clone(), b.clone()));
+            last_join = Some((*a, *b));
             break;
         }
     }
-    last_join.unwrap().0.x * last_join.unwrap().1.x
+    Ok(last_join.unwrap().0.x * last_join.unwrap().1.x)
+}
+
+fn generate(n: usize) -> String {
+    let mut rng = scaling::Rng::new(n as u64);
+    (0..n)
+        .map(|_| {
+            let [x, y, z] = [(); 3].map(|_| rng.below(100_000));
+            format!("{},{},{}", x, y, z)
+        })
+        .join("\n")
 }
 
 fn main() {
@@ -122,6 +124,8 @@ fn main() {
     part1_answer!(1000, 66640);
     part2_test!(25272);
     part2_answer!(78894156);
+    part1_scaling!(1000, generate, [250, 500, 1000, 2000]);
+    part2_scaling!(generate, [250, 500, 1000, 2000]);
 }
 
 #[test]
@@ -1,20 +1,22 @@
 use itertools::Itertools;
+use utils::parse::{FromLine, ParseResult};
 use utils::*;
 
-fn part1(input: &str) -> usize {
-    input
-        .lines()
-        .map(|line| {
-            let mut split = line.split(",");
-            (
-                split.next().unwrap().parse::<usize>().unwrap(),
-                split.next().unwrap().parse::<usize>().unwrap(),
-            )
-        })
+#[derive(FromLine)]
+#[line("{},{}")]
+struct Tile(usize, usize);
+
+fn parse_tiles(input: &str) -> ParseResult<Vec<(usize, usize)>> {
+    parse::lines(input, |p| Tile::parse_line(p).map(|Tile(x, y)| (x, y)))
+}
+
+fn part1(input: &str) -> ParseResult<usize> {
+    Ok(parse_tiles(input)?
+        .into_iter()
         .tuple_combinations()
         .map(|(a, b)| ((a.0.max(b.0) - a.0.min(b.0)) + 1) * ((a.1.max(b.1) - a.1.min(b.1)) + 1))
         .max()
-        .unwrap()
+        .unwrap())
 }
 
 enum Edge {
@@ -26,18 +28,14 @@ impl Edge {
     fn intersects_rect(&self, l: usize, t: usize, r: usize, b: usize) -> bool {
         match self {
             Edge::Vertical { x, start, end } => {
-                if *x > l && *x < r {
-                    if (start < &t && end > &t) || (start < &b && end > &b) {
-                        return true;
-                    }
+                if *x > l && *x < r && ((start < &t && end > &t) || (start < &b && end > &b)) {
+                    return true;
                 }
                 false
             }
             Edge::Horizontal { y, start, end } => {
-                if *y > t && *y < b {
-                    if (start < &l && end > &l) || (start < &r && end > &r) {
-                        return true;
-                    }
+                if *y > t && *y < b && ((start < &l && end > &l) || (start < &r && end > &r)) {
+                    return true;
                 }
                 false
             }
@@ -45,16 +43,9 @@ impl Edge {
     }
 }
 
-fn part2(input: &str) -> usize {
-    let combinations = input
-        .lines()
-        .map(|line| {
-            let mut split = line.split(",");
-            (
-                split.next().unwrap().parse::<usize>().unwrap(),
-                split.next().unwrap().parse::<usize>().unwrap(),
-            )
-        })
+fn part2(input: &str) -> ParseResult<usize> {
+    let combinations = parse_tiles(input)?
+        .into_iter()
         .tuple_combinations()
         .collect::<Vec<((usize, usize), (usize, usize))>>();
 
@@ -79,7 +70,7 @@ fn part2(input: &str) -> usize {
         })
         .collect::<Vec<_>>();
 
-    combinations
+    Ok(combinations
         .iter()
         .filter_map(|(a, b)| {
             let left = a.0.min(b.0);
@@ -93,7 +84,7 @@ fn part2(input: &str) -> usize {
             .then_some(area)
         })
         .max()
-        .unwrap()
+        .unwrap())
 }
 
 fn main() {
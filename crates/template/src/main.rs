@@ -1,10 +1,11 @@
 use utils::*;
 
-fn part1(input: &str) -> usize {
+fn part1(_input: &str) -> usize {
     0
 }
 
-fn part2(input: &str) -> usize {
+#[allow(dead_code)]
+fn part2(_input: &str) -> usize {
     0
 }
 
@@ -0,0 +1,369 @@
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{format_ident, quote};
+use syn::parse::{Parse, ParseStream};
+use syn::{
+    parse_macro_input, parse_quote, Attribute, Data, DeriveInput, Expr, Fields, Lifetime, LitChar,
+    LitStr, Token, Type,
+};
+
+/// Derives `utils::parse::FromLine` and a fallible `FromStr` from a `#[line("...")]` pattern.
+///
+/// Placeholders are `{name}` for named fields and `{}` or `{0}` for tuple fields,
+/// everything else must match literally (`{{` and `}}` escape braces).
+/// Every field is parsed with its own `FromLine` impl. If the type has a lifetime
+/// parameter, it is used as the lifetime of the line, so fields can borrow from it.
+/// Enums need a pattern on every variant, which are tried in order.
+#[proc_macro_derive(FromLine, attributes(line))]
+pub fn derive_from_line(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand(&input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+fn expand(input: &DeriveInput) -> syn::Result<TokenStream2> {
+    let name = &input.ident;
+    let mut generics = input.generics.clone();
+    let lifetime = match input.generics.lifetimes().next() {
+        Some(param) => param.lifetime.clone(),
+        None => {
+            let lifetime = Lifetime::new("'__line", proc_macro2::Span::call_site());
+            generics.params.insert(0, parse_quote!(#lifetime));
+            lifetime
+        }
+    };
+    let body = match &input.data {
+        Data::Struct(data) => {
+            let pattern = pattern(&input.attrs, name)?;
+            parse_fields(&data.fields, &pattern, &lifetime, quote!(Self))?
+        }
+        Data::Enum(data) => {
+            if data.variants.is_empty() {
+                return Err(syn::Error::new_spanned(
+                    name,
+                    "FromLine needs at least one variant",
+                ));
+            }
+            let alternatives = data
+                .variants
+                .iter()
+                .map(|variant| {
+                    let pattern = pattern(&variant.attrs, &variant.ident)?;
+                    let ident = &variant.ident;
+                    let body =
+                        parse_fields(&variant.fields, &pattern, &lifetime, quote!(Self::#ident))?;
+                    Ok(quote! {
+                        |p: &mut ::utils::parse::Parser<#lifetime>| -> ::utils::parse::ParseResult<Self> {
+                            #body
+                        }
+                    })
+                })
+                .collect::<syn::Result<Vec<_>>>()?;
+            quote!(p.alt(&[#(#alternatives),*]))
+        }
+        Data::Union(_) => {
+            return Err(syn::Error::new_spanned(
+                name,
+                "FromLine cannot be derived for unions",
+            ))
+        }
+    };
+    let (impl_generics, _, _) = generics.split_for_impl();
+    let (from_str_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    // Types borrowing from the line cannot implement `FromStr`.
+    let from_str = (input.generics.lifetimes().next().is_none()).then(|| {
+        quote! {
+            impl #from_str_generics ::std::str::FromStr for #name #ty_generics #where_clause {
+                type Err = ::utils::parse::ParseError;
+
+                fn from_str(s: &str) -> ::std::result::Result<Self, Self::Err> {
+                    ::utils::parse::from_line(s)
+                }
+            }
+        }
+    });
+    Ok(quote! {
+        impl #impl_generics ::utils::parse::FromLine<#lifetime> for #name #ty_generics #where_clause {
+            fn parse_line(p: &mut ::utils::parse::Parser<#lifetime>) -> ::utils::parse::ParseResult<Self> {
+                #body
+            }
+        }
+
+        #from_str
+    })
+}
+
+fn pattern(attrs: &[Attribute], owner: &syn::Ident) -> syn::Result<LitStr> {
+    attrs
+        .iter()
+        .find(|attr| attr.path().is_ident("line"))
+        .ok_or_else(|| syn::Error::new_spanned(owner, "missing #[line(\"...\")] pattern"))?
+        .parse_args()
+}
+
+enum Segment {
+    Literal(String),
+    /// The text inside the braces, empty for `{}`.
+    Field(String),
+}
+
+fn segments(pattern: &LitStr) -> syn::Result<Vec<Segment>> {
+    let text = pattern.value();
+    if text.is_empty() {
+        return Err(syn::Error::new_spanned(
+            pattern,
+            "pattern must not be empty",
+        ));
+    }
+    let mut segments = vec![];
+    let mut literal = String::new();
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '{' if chars.peek() == Some(&'{') => {
+                chars.next();
+                literal.push('{');
+            }
+            '}' if chars.peek() == Some(&'}') => {
+                chars.next();
+                literal.push('}');
+            }
+            '{' => {
+                let mut field = String::new();
+                loop {
+                    match chars.next() {
+                        Some('}') => break,
+                        Some(c) => field.push(c),
+                        None => return Err(syn::Error::new_spanned(pattern, "unclosed `{`")),
+                    }
+                }
+                if !literal.is_empty() {
+                    segments.push(Segment::Literal(std::mem::take(&mut literal)));
+                }
+                segments.push(Segment::Field(field.trim().to_string()));
+            }
+            '}' => return Err(syn::Error::new_spanned(pattern, "unmatched `}`, use `}}`")),
+            c => literal.push(c),
+        }
+    }
+    if !literal.is_empty() {
+        segments.push(Segment::Literal(literal));
+    }
+    Ok(segments)
+}
+
+/// Statements parsing `fields` in pattern order, ending in `constructor { .. }`.
+fn parse_fields(
+    fields: &Fields,
+    pattern: &LitStr,
+    lifetime: &Lifetime,
+    constructor: TokenStream2,
+) -> syn::Result<TokenStream2> {
+    let names = fields
+        .iter()
+        .enumerate()
+        .map(|(i, field)| match &field.ident {
+            Some(ident) => ident.to_string(),
+            None => i.to_string(),
+        })
+        .collect::<Vec<_>>();
+    let mut used = vec![false; names.len()];
+    let mut next_positional = 0;
+    let mut statements = vec![];
+    for segment in segments(pattern)? {
+        match segment {
+            Segment::Literal(text) => statements.push(quote!(p.tag(#text)?;)),
+            Segment::Field(name) => {
+                let name = if name.is_empty() {
+                    if matches!(fields, Fields::Named(_)) {
+                        return Err(syn::Error::new_spanned(
+                            pattern,
+                            "named fields need `{name}` placeholders",
+                        ));
+                    }
+                    next_positional += 1;
+                    (next_positional - 1).to_string()
+                } else {
+                    name
+                };
+                let Some(index) = names.iter().position(|n| *n == name) else {
+                    return Err(syn::Error::new_spanned(
+                        pattern,
+                        format!("no field `{}`", name),
+                    ));
+                };
+                if std::mem::replace(&mut used[index], true) {
+                    return Err(syn::Error::new_spanned(
+                        pattern,
+                        format!("field `{}` appears twice", name),
+                    ));
+                }
+                let var = format_ident!("__field{}", index);
+                let ty = &fields.iter().nth(index).unwrap().ty;
+                statements
+                    .push(quote!(let #var = <#ty as ::utils::parse::FromLine<#lifetime>>::parse_line(p)?;));
+            }
+        }
+    }
+    if let Some(index) = used.iter().position(|used| !used) {
+        return Err(syn::Error::new_spanned(
+            pattern,
+            format!("field `{}` is missing from the pattern", names[index]),
+        ));
+    }
+    let vars = (0..names.len()).map(|i| format_ident!("__field{}", i));
+    let value = match fields {
+        Fields::Named(named) => {
+            let idents = named.named.iter().map(|field| &field.ident);
+            quote!(#constructor { #(#idents: #vars),* })
+        }
+        Fields::Unnamed(_) => quote!(#constructor(#(#vars),*)),
+        Fields::Unit => constructor,
+    };
+    Ok(quote! {
+        #(#statements)*
+        ::std::result::Result::Ok(#value)
+    })
+}
+
+struct ScanInput {
+    line: Expr,
+    pattern: LitStr,
+}
+
+impl Parse for ScanInput {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let line = input.parse()?;
+        input.parse::<Token![,]>()?;
+        let pattern = input.parse()?;
+        input.parse::<Option<Token![,]>>()?;
+        Ok(Self { line, pattern })
+    }
+}
+
+/// Parses a line against a pattern, returning `ParseResult` of a tuple with one
+/// value per placeholder (a bare value for a single placeholder).
+///
+/// `{}` infers the type, `{usize}` names it, and a final `{..}` captures the rest
+/// of the line as `&str`. Every other character must match literally.
+#[proc_macro]
+pub fn scan(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as ScanInput);
+    expand_scan(&input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+fn expand_scan(input: &ScanInput) -> syn::Result<TokenStream2> {
+    let pattern = &input.pattern;
+    let segments = segments(pattern)?;
+    let mut statements = vec![];
+    let mut vars = vec![];
+    for (i, segment) in segments.iter().enumerate() {
+        match segment {
+            Segment::Literal(text) => statements.push(quote!(__parser.tag(#text)?;)),
+            Segment::Field(field) => {
+                let var = format_ident!("__value{}", vars.len());
+                statements.push(match field.as_str() {
+                    "" => quote!(let #var = ::utils::parse::FromLine::parse_line(&mut __parser)?;),
+                    ".." if i + 1 == segments.len() => quote!(let #var = __parser.rest();),
+                    ".." => {
+                        return Err(syn::Error::new_spanned(
+                            pattern,
+                            "`{..}` must be the last placeholder",
+                        ))
+                    }
+                    ty => {
+                        let ty = syn::parse_str::<Type>(ty).map_err(|_| {
+                            syn::Error::new_spanned(pattern, format!("`{}` is not a type", ty))
+                        })?;
+                        quote!(let #var = <#ty as ::utils::parse::FromLine>::parse_line(&mut __parser)?;)
+                    }
+                });
+                vars.push(var);
+            }
+        }
+    }
+    let value = match vars.as_slice() {
+        [var] => quote!(#var),
+        vars => quote!((#(#vars),*)),
+    };
+    let line = &input.line;
+    Ok(quote! {{
+        let __line: &str = #line;
+        (|| {
+            let mut __parser = ::utils::parse::Parser::new(__line, 1);
+            #(#statements)*
+            __parser.end()?;
+            ::std::result::Result::<_, ::utils::parse::ParseError>::Ok(#value)
+        })()
+    }})
+}
+
+/// Derives `utils::grid::GridCell` and `Display` for an enum of unit variants,
+/// each marked with the character it is drawn as, e.g. `#[cell('@')]`.
+#[proc_macro_derive(GridCell, attributes(cell))]
+pub fn derive_grid_cell(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand_grid_cell(&input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+fn expand_grid_cell(input: &DeriveInput) -> syn::Result<TokenStream2> {
+    let name = &input.ident;
+    let Data::Enum(data) = &input.data else {
+        return Err(syn::Error::new_spanned(
+            name,
+            "GridCell can only be derived for enums",
+        ));
+    };
+    let mut variants = vec![];
+    let mut chars: Vec<LitChar> = vec![];
+    for variant in &data.variants {
+        if !matches!(variant.fields, Fields::Unit) {
+            return Err(syn::Error::new_spanned(
+                variant,
+                "GridCell variants cannot have fields",
+            ));
+        }
+        let c: LitChar = variant
+            .attrs
+            .iter()
+            .find(|attr| attr.path().is_ident("cell"))
+            .ok_or_else(|| syn::Error::new_spanned(variant, "missing #[cell('.')] character"))?
+            .parse_args()?;
+        if chars.iter().any(|other| other.value() == c.value()) {
+            return Err(syn::Error::new_spanned(
+                &c,
+                format!("{:?} is used by another variant", c.value()),
+            ));
+        }
+        variants.push(&variant.ident);
+        chars.push(c);
+    }
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    Ok(quote! {
+        impl #impl_generics ::utils::grid::GridCell for #name #ty_generics #where_clause {
+            fn from_char(c: char) -> ::std::option::Option<Self> {
+                match c {
+                    #(#chars => ::std::option::Option::Some(Self::#variants),)*
+                    _ => ::std::option::Option::None,
+                }
+            }
+
+            fn to_char(&self) -> char {
+                match self {
+                    #(Self::#variants => #chars,)*
+                }
+            }
+        }
+
+        impl #impl_generics ::std::fmt::Display for #name #ty_generics #where_clause {
+            fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+                ::std::write!(f, "{}", ::utils::grid::GridCell::to_char(self))
+            }
+        }
+    })
+}
@@ -0,0 +1,139 @@
+//! Encryption of puzzle inputs so they can be committed without sharing them.
+//!
+//! Every [`encrypt`] call picks a random nonce, so encrypting the same input twice
+//! gives different files. `aoc encrypt` skips files that are already encrypted,
+//! so an input only shows up as changed after it was decrypted and encrypted again.
+
+use std::fmt::Display;
+
+use base64::prelude::{Engine, BASE64_STANDARD};
+use chacha20poly1305::aead::{Aead, AeadCore, OsRng};
+use chacha20poly1305::{ChaCha20Poly1305, Key, KeyInit, Nonce};
+
+/// Random 32-byte key, base64 encoded, used to encrypt and decrypt puzzle inputs.
+/// Generate one with `aoc keygen`.
+pub const KEY_ENV: &str = "AOC_INPUT_KEY";
+
+const BEGIN: &str = "-----BEGIN AOC ENCRYPTED INPUT-----";
+const END: &str = "-----END AOC ENCRYPTED INPUT-----";
+const NONCE_LEN: usize = 12;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CryptError {
+    Malformed,
+    WrongKey,
+    NotUtf8,
+    InvalidKey,
+}
+
+impl Display for CryptError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Malformed => write!(f, "encrypted input is malformed"),
+            Self::WrongKey => write!(f, "could not decrypt input, is {} correct?", KEY_ENV),
+            Self::NotUtf8 => write!(f, "decrypted input is not valid UTF-8"),
+            Self::InvalidKey => write!(f, "{} must be a base64 encoded 32-byte key", KEY_ENV),
+        }
+    }
+}
+
+impl std::error::Error for CryptError {}
+
+pub struct InputKey(Key);
+
+impl InputKey {
+    /// Generates a new random key.
+    pub fn generate() -> Self {
+        Self(ChaCha20Poly1305::generate_key(&mut OsRng))
+    }
+
+    /// Decodes a key written by [`InputKey::to_base64`].
+    /// # Examples
+    /// ```
+    /// use utils::crypt::{CryptError, InputKey};
+    ///
+    /// let key = InputKey::generate();
+    /// assert!(InputKey::from_base64(&key.to_base64()).is_ok());
+    /// assert_eq!(InputKey::from_base64("hunter2").err(), Some(CryptError::InvalidKey));
+    /// assert_eq!(InputKey::from_base64("aHVudGVyMg==").err(), Some(CryptError::InvalidKey));
+    /// ```
+    pub fn from_base64(encoded: &str) -> Result<Self, CryptError> {
+        let bytes = BASE64_STANDARD
+            .decode(encoded.trim())
+            .map_err(|_| CryptError::InvalidKey)?;
+        if bytes.len() != 32 {
+            return Err(CryptError::InvalidKey);
+        }
+        Ok(Self(*Key::from_slice(&bytes)))
+    }
+
+    pub fn to_base64(&self) -> String {
+        BASE64_STANDARD.encode(self.0)
+    }
+
+    /// Reads the key from [`KEY_ENV`]. Returns `None` if it is unset or empty.
+    pub fn from_env() -> Option<Result<Self, CryptError>> {
+        std::env::var(KEY_ENV)
+            .ok()
+            .filter(|encoded| !encoded.is_empty())
+            .map(|encoded| Self::from_base64(&encoded))
+    }
+}
+
+pub fn is_encrypted(text: &str) -> bool {
+    text.trim_start().starts_with(BEGIN)
+}
+
+/// Encrypts with ChaCha20-Poly1305 into an ASCII-armored block that can still be `include_str!`-ed.
+/// # Examples
+/// ```
+/// use utils::crypt::{decrypt, encrypt, is_encrypted, CryptError, InputKey};
+///
+/// let key = InputKey::generate();
+/// let armored = encrypt("L68\nR48\n", &key);
+/// assert!(is_encrypted(&armored));
+/// assert_ne!(armored, encrypt("L68\nR48\n", &key));
+/// assert_eq!(decrypt(&armored, &key).unwrap(), "L68\nR48\n");
+/// assert_eq!(
+///     decrypt(&armored, &InputKey::generate()),
+///     Err(CryptError::WrongKey)
+/// );
+/// ```
+pub fn encrypt(plaintext: &str, key: &InputKey) -> String {
+    let nonce = ChaCha20Poly1305::generate_nonce(&mut OsRng);
+    let ciphertext = ChaCha20Poly1305::new(&key.0)
+        .encrypt(&nonce, plaintext.as_bytes())
+        .expect("Encryption failed");
+
+    let mut data = nonce.to_vec();
+    data.extend(ciphertext);
+    let encoded = BASE64_STANDARD.encode(data);
+    let mut armored = format!("{}\n", BEGIN);
+    for chunk in encoded.as_bytes().chunks(64) {
+        armored.push_str(std::str::from_utf8(chunk).unwrap());
+        armored.push('\n');
+    }
+    armored.push_str(END);
+    armored.push('\n');
+    armored
+}
+
+pub fn decrypt(armored: &str, key: &InputKey) -> Result<String, CryptError> {
+    let body = armored
+        .trim()
+        .strip_prefix(BEGIN)
+        .and_then(|rest| rest.strip_suffix(END))
+        .ok_or(CryptError::Malformed)?;
+    let encoded = body.split_whitespace().collect::<String>();
+    let data = BASE64_STANDARD
+        .decode(encoded)
+        .map_err(|_| CryptError::Malformed)?;
+    if data.len() < NONCE_LEN {
+        return Err(CryptError::Malformed);
+    }
+    let (nonce, ciphertext) = data.split_at(NONCE_LEN);
+    let plaintext = ChaCha20Poly1305::new(&key.0)
+        .decrypt(Nonce::from_slice(nonce), ciphertext)
+        .map_err(|_| CryptError::WrongKey)?;
+    String::from_utf8(plaintext).map_err(|_| CryptError::NotUtf8)
+}
@@ -15,6 +15,17 @@ pub trait Direction: Sized {
         let (dy, dx) = self.dydx(distance);
         (pos.0 as isize + dy, pos.1 as isize + dx)
     }
+
+    /// Whether some direction moves along both axes at once.
+    fn has_diagonals() -> bool
+    where
+        Self: 'static,
+    {
+        Self::all().iter().any(|d| {
+            let (dy, dx) = d.dydx(1);
+            dy != 0 && dx != 0
+        })
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
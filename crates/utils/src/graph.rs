@@ -0,0 +1,187 @@
+use crate::intern::{Id, IdMap, Interner};
+use crate::parse::{FromLine, ParseError, ParseResult, Parser};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EdgeMode {
+    Directed,
+    /// Every edge is also added in the opposite direction.
+    Undirected,
+}
+
+/// Graph with nodes numbered in order of first appearance, borrowing names from the input.
+#[derive(Debug, Clone)]
+pub struct Graph<'a, W = ()> {
+    names: Interner<'a>,
+    edges: IdMap<Vec<(Id, W)>>,
+    /// Lines read by [`Graph::parse`], so a missing node can be reported after them.
+    input_lines: usize,
+}
+
+impl<'a, W> Default for Graph<'a, W> {
+    fn default() -> Self {
+        Self {
+            names: Interner::new(),
+            edges: IdMap::new(),
+            input_lines: 0,
+        }
+    }
+}
+
+impl<'a> Graph<'a> {
+    /// Reads `name: a b c` adjacency lines. Nodes that only appear as targets
+    /// become sinks without edges, and a name listed twice is an error.
+    /// # Examples
+    /// ```
+    /// use utils::graph::{EdgeMode, Graph};
+    ///
+    /// let graph = Graph::parse("you: bbb ccc\nbbb: out\nccc: out", EdgeMode::Directed).unwrap();
+    /// assert_eq!(graph.len(), 4);
+    /// let out = graph.node("out").unwrap();
+    /// assert!(graph.neighbors(out).next().is_none());
+    /// assert_eq!(graph.neighbor_names("you").collect::<Vec<_>>(), ["bbb", "ccc"]);
+    ///
+    /// let undirected = Graph::parse("a: b\nb: c", EdgeMode::Undirected).unwrap();
+    /// assert_eq!(undirected.neighbor_names("b").collect::<Vec<_>>(), ["a", "c"]);
+    ///
+    /// let err = Graph::parse("a: b\nb: c\na: c", EdgeMode::Directed).unwrap_err();
+    /// assert_eq!((err.line, err.column, err.expected.as_str()), (3, 1, "a node not listed before"));
+    /// ```
+    pub fn parse(input: &'a str, mode: EdgeMode) -> ParseResult<Self> {
+        Self::parse_with(input, mode, |_| Ok(()))
+    }
+}
+
+impl<'a, W: FromLine<'a> + Clone> Graph<'a, W> {
+    /// Like [`Graph::parse`], with a weight after every target, as in `a: b=3 c=10`.
+    /// # Examples
+    /// ```
+    /// use utils::graph::{EdgeMode, Graph};
+    ///
+    /// let graph = Graph::<u32>::parse_weighted("a: b=3 c=10\nb: c=2", EdgeMode::Directed).unwrap();
+    /// let a = graph.node("a").unwrap();
+    /// let weights = graph.edges(a).iter().map(|&(to, w)| (graph.name(to), w));
+    /// assert_eq!(weights.collect::<Vec<_>>(), [("b", 3), ("c", 10)]);
+    /// assert!(Graph::<u32>::parse_weighted("a: b", EdgeMode::Directed).is_err());
+    /// ```
+    pub fn parse_weighted(input: &'a str, mode: EdgeMode) -> ParseResult<Self> {
+        Self::parse_with(input, mode, |p| {
+            p.tag("=")?;
+            W::parse_line(p)
+        })
+    }
+}
+
+impl<'a, W: Clone> Graph<'a, W> {
+    fn parse_with(
+        input: &'a str,
+        mode: EdgeMode,
+        mut weight: impl FnMut(&mut Parser<'a>) -> ParseResult<W>,
+    ) -> ParseResult<Self> {
+        let _span = tracing::info_span!("parse").entered();
+        let mut graph = Self::default();
+        let mut listed = IdMap::new();
+        for (i, line) in input.lines().enumerate() {
+            let mut p = Parser::new(line, i + 1);
+            let name = p.key(":")?.trim();
+            let from = graph.add_node(name);
+            if std::mem::replace(listed.entry(from), true) {
+                return Err(ParseError::at(i + 1, line, 0, "a node not listed before"));
+            }
+            while !p.skip_whitespace().is_empty() {
+                let start = p.clone();
+                let target = p.take_while(|c| !c.is_whitespace() && c != '=');
+                if target.is_empty() {
+                    return Err(start.error("a node name"));
+                }
+                let to = graph.add_node(target);
+                let weight = weight(&mut p)?;
+                if mode == EdgeMode::Undirected {
+                    graph.edges[to].push((from, weight.clone()));
+                }
+                graph.edges[from].push((to, weight));
+            }
+            graph.input_lines = i + 1;
+        }
+        Ok(graph)
+    }
+}
+
+impl<'a, W> Graph<'a, W> {
+    /// Returns the node's id, adding it if it is new.
+    pub fn add_node(&mut self, name: &'a str) -> Id {
+        let id = self.names.intern(name);
+        self.edges.entry(id);
+        id
+    }
+
+    pub fn add_edge(&mut self, from: Id, to: Id, weight: W) {
+        self.edges[from].push((to, weight));
+    }
+
+    pub fn node(&self, name: &str) -> Option<Id> {
+        self.names.get(name)
+    }
+
+    /// Like [`Graph::node`], for nodes the input has to contain. A missing
+    /// node is an error at the end of the input.
+    /// # Examples
+    /// ```
+    /// use utils::graph::{EdgeMode, Graph};
+    ///
+    /// let graph = Graph::parse("you: out\nout: end", EdgeMode::Directed).unwrap();
+    /// assert_eq!(graph.require_node("you"), Ok(graph.node("you").unwrap()));
+    /// let err = graph.require_node("svr").unwrap_err();
+    /// assert_eq!(
+    ///     err.to_string().lines().next(),
+    ///     Some("line 3, column 1: expected a node named \"svr\", found end of line")
+    /// );
+    /// ```
+    pub fn require_node(&self, name: &str) -> ParseResult<Id> {
+        self.node(name).ok_or_else(|| {
+            ParseError::at(
+                self.input_lines + 1,
+                "",
+                0,
+                format!("a node named {:?}", name),
+            )
+        })
+    }
+
+    pub fn name(&self, node: Id) -> &'a str {
+        self.names.resolve(node)
+    }
+
+    pub fn len(&self) -> usize {
+        self.names.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.names.is_empty()
+    }
+
+    /// Every node id, in order of first appearance.
+    pub fn nodes(&self) -> impl Iterator<Item = Id> + '_ {
+        self.names.iter().map(|(id, _)| id)
+    }
+
+    /// Outgoing edges with their weights.
+    pub fn edges(&self, node: Id) -> &[(Id, W)] {
+        &self.edges[node]
+    }
+
+    pub fn neighbors(&self, node: Id) -> impl Iterator<Item = Id> + '_ {
+        self.edges[node].iter().map(|&(to, _)| to)
+    }
+
+    /// Names of the nodes reachable in one step from `name`, empty if it is unknown.
+    pub fn neighbor_names(&self, name: &str) -> impl Iterator<Item = &'a str> + '_ {
+        self.node(name)
+            .into_iter()
+            .flat_map(|node| self.neighbors(node))
+            .map(|node| self.name(node))
+    }
+
+    pub fn is_sink(&self, node: Id) -> bool {
+        self.edges[node].is_empty()
+    }
+}
@@ -1,15 +1,32 @@
 use num::{Integer, Signed, Unsigned};
 
 use crate::direction::{CardinalDirection, Direction, OrdinalDirection, PositionVirtual};
+use crate::ints::Int;
+use crate::parse::{ParseError, ParseResult};
+use crate::sections::{sections, Section};
 use itertools::Itertools;
 use std::borrow::Borrow;
 use std::collections::HashMap;
 use std::fmt::{Debug, Display};
 use std::hash::Hash;
 use std::marker::PhantomData;
-use std::ops::{Add, Div, Index, IndexMut, Mul, Sub};
+use std::ops::{Add, Div, Index, IndexMut, Mul, Range, Sub};
+
+pub mod automaton;
+mod bits;
+mod sparse;
+mod symmetry;
+mod wrapping;
+
+pub use bits::BitGrid;
+pub use sparse::SparseGrid;
+pub use symmetry::{Symmetry, TransformedView};
+pub use utils_derive::GridCell;
+pub use wrapping::WrappingGrid;
 
 pub type Position = (usize, usize);
+/// Labelled grids and the unlabelled section after them, see [`Grid::labelled_blocks`].
+pub type LabelledBlocks<'a, T> = (Vec<(&'a str, Grid<T>)>, Option<Section<'a>>);
 
 #[derive(Debug, Clone)]
 pub struct Grid<T: Debug + Clone> {
@@ -58,31 +75,312 @@ impl<T: Debug + Clone> IndexMut<PositionVirtual> for Grid<T> {
     }
 }
 
+/// A cell that is drawn as a single character, see [`Grid::parse`].
+/// Usually derived, with `#[cell('#')]` on every variant of an enum.
+pub trait GridCell: Sized {
+    /// `None` if `c` is not a valid cell.
+    fn from_char(c: char) -> Option<Self>;
+    fn to_char(&self) -> char;
+}
+
+impl GridCell for char {
+    fn from_char(c: char) -> Option<Self> {
+        Some(c)
+    }
+
+    fn to_char(&self) -> char {
+        *self
+    }
+}
+
+/// Why a grid could not be built. Rows and columns are 0-based, like [`Position`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GridError {
+    Empty,
+    /// A row is longer or shorter than the first one, from `column` on. When
+    /// reading text, `found` is the first character past the expected width.
+    Ragged {
+        row: usize,
+        column: usize,
+        width: usize,
+        found: Option<char>,
+    },
+    /// The cell mapper rejected a character.
+    InvalidCell {
+        row: usize,
+        column: usize,
+        found: char,
+    },
+    /// A cell that has to be present, such as a start marker, is not.
+    MissingCell {
+        cell: char,
+    },
+}
+
+impl Display for GridError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Empty => write!(f, "empty grid"),
+            Self::Ragged {
+                row,
+                column,
+                width,
+                found: Some(c),
+            } => write!(
+                f,
+                "row {} is longer than {} cells, found {:?} at column {}",
+                row, width, c, column
+            ),
+            Self::Ragged {
+                row, column, width, ..
+            } => write!(
+                f,
+                "row {} is not {} cells wide, mismatch at column {}",
+                row, width, column
+            ),
+            Self::InvalidCell { row, column, found } => {
+                write!(
+                    f,
+                    "invalid cell {:?} at row {}, column {}",
+                    found, row, column
+                )
+            }
+            Self::MissingCell { cell } => write!(f, "no {:?} cell in the grid", cell),
+        }
+    }
+}
+
+impl std::error::Error for GridError {}
+
+impl GridError {
+    /// Converts the error for a grid read from `section` into a [`ParseError`]
+    /// at the offending line and column of the whole input.
+    pub fn in_section(&self, section: &Section) -> ParseError {
+        let (row, column, expected) = match *self {
+            Self::Empty => return ParseError::at(section.line, "", 0, "a grid"),
+            Self::MissingCell { cell } => {
+                return ParseError::at(section.line, "", 0, format!("a {:?} cell", cell))
+            }
+            Self::Ragged {
+                row,
+                column,
+                width,
+                found: Some(_),
+            } => (row, column, format!("end of row after {} cells", width)),
+            Self::Ragged {
+                row, column, width, ..
+            } => (row, column, format!("a row of {} cells", width)),
+            Self::InvalidCell { row, column, .. } => (row, column, "a grid cell".to_string()),
+        };
+        let source_line = section.lines().nth(row).unwrap_or_default();
+        let start = source_line
+            .char_indices()
+            .nth(column)
+            .map_or(source_line.len(), |(i, _)| i);
+        ParseError::at(section.line + row, source_line, start, expected)
+    }
+}
+
+/// Checks that there is at least one row, every row has the same non-zero
+/// length, and returns `(height, width)`.
+fn check_rows(lens: impl Iterator<Item = usize>) -> Result<(usize, usize), GridError> {
+    let mut height = 0;
+    let mut width = 0;
+    for (row, len) in lens.enumerate() {
+        if row == 0 {
+            if len == 0 {
+                return Err(GridError::Empty);
+            }
+            width = len;
+        } else if len != width {
+            return Err(GridError::Ragged {
+                row,
+                column: len.min(width),
+                width,
+                found: None,
+            });
+        }
+        height += 1;
+    }
+    if height == 0 || width == 0 {
+        return Err(GridError::Empty);
+    }
+    Ok((height, width))
+}
+
 impl<T: Debug + Clone> Grid<T> {
+    /// Panics on ragged or empty input, see [`Grid::try_from_str`].
     pub fn from_str(input: &str, f: impl Fn(char) -> T) -> Self {
+        Self::try_from_str(input, |c| Some(f(c))).unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    /// Reads a grid of [`GridCell`]s. Printing it gives back the input, one
+    /// line ending per row.
+    /// # Examples
+    /// ```
+    /// use utils::grid::{Grid, GridCell, GridError};
+    ///
+    /// #[derive(Debug, Clone, Copy, PartialEq, Eq, GridCell)]
+    /// enum Cell {
+    ///     #[cell('.')]
+    ///     Empty,
+    ///     #[cell('@')]
+    ///     Paper,
+    /// }
+    ///
+    /// let input = "..@\n@@.\n";
+    /// let grid = Grid::<Cell>::parse(input).unwrap();
+    /// assert_eq!((grid[(0usize, 2usize)], grid.to_string()), (Cell::Paper, input.to_string()));
+    /// assert_eq!(Cell::Paper.to_char(), '@');
+    ///
+    /// let err = Grid::<Cell>::parse("..\n.#").unwrap_err();
+    /// assert_eq!(err, GridError::InvalidCell { row: 1, column: 1, found: '#' });
+    /// ```
+    pub fn parse(input: &str) -> Result<Self, GridError>
+    where
+        T: GridCell,
+    {
+        Self::try_from_str(input, T::from_char)
+    }
+
+    /// Position of the first `cell`, for markers that must be present.
+    /// # Examples
+    /// ```
+    /// use utils::grid::{Grid, GridError};
+    ///
+    /// let grid = Grid::char_grid(".S\n..");
+    /// assert_eq!(grid.find_cell('S'), Ok((0, 1)));
+    /// assert_eq!(grid.find_cell('E'), Err(GridError::MissingCell { cell: 'E' }));
+    /// ```
+    pub fn find_cell(&self, cell: T) -> Result<Position, GridError>
+    where
+        T: GridCell + PartialEq,
+    {
+        self.find(|t| *t == cell).ok_or(GridError::MissingCell {
+            cell: cell.to_char(),
+        })
+    }
+
+    /// Like [`Grid::from_str`], but `f` may reject a character by returning `None`.
+    /// # Examples
+    /// ```
+    /// use utils::grid::{Grid, GridError};
+    ///
+    /// let cell = |c| match c {
+    ///     '.' => Some(false),
+    ///     '@' => Some(true),
+    ///     _ => None,
+    /// };
+    /// assert_eq!(Grid::try_from_str(".@\n@.", cell).unwrap().size(), (2, 2));
+    ///
+    /// let err = Grid::try_from_str(".@\n@x", cell).unwrap_err();
+    /// assert_eq!(err, GridError::InvalidCell { row: 1, column: 1, found: 'x' });
+    /// assert_eq!(err.to_string(), "invalid cell 'x' at row 1, column 1");
+    ///
+    /// let err = Grid::try_from_str(".@\n@..", cell).unwrap_err();
+    /// assert_eq!(err, GridError::Ragged { row: 1, column: 2, width: 2, found: Some('.') });
+    /// assert_eq!(Grid::try_from_str("", cell).unwrap_err(), GridError::Empty);
+    /// assert_eq!(Grid::try_from_str("\n.@", cell).unwrap_err(), GridError::Empty);
+    /// ```
+    pub fn try_from_str(input: &str, f: impl Fn(char) -> Option<T>) -> Result<Self, GridError> {
+        let _span = tracing::info_span!("parse").entered();
         let mut data = Vec::with_capacity(input.len());
+        let mut width = None;
         let mut height = 0;
-        let mut width = 0;
-        for (y, line) in input.lines().enumerate() {
-            if y == 0 {
-                width = line.len();
-            } else {
-                assert_eq!(line.len(), width, "Inconsistent row length!");
+        for (row, line) in input.lines().enumerate() {
+            let mut len = 0;
+            for (column, c) in line.chars().enumerate() {
+                if width.is_some_and(|width| column >= width) {
+                    return Err(GridError::Ragged {
+                        row,
+                        column,
+                        width: width.unwrap(),
+                        found: Some(c),
+                    });
+                }
+                data.push(f(c).ok_or(GridError::InvalidCell {
+                    row,
+                    column,
+                    found: c,
+                })?);
+                len += 1;
             }
-            let mut row = vec![];
-            for c in line.chars() {
-                row.push(f(c));
+            match width {
+                None if len == 0 => return Err(GridError::Empty),
+                None => width = Some(len),
+                Some(width) if len < width => {
+                    return Err(GridError::Ragged {
+                        row,
+                        column: len,
+                        width,
+                        found: None,
+                    });
+                }
+                Some(_) => {}
             }
-            data.extend(row);
             height += 1;
         }
-        assert_ne!(height, 0, "Empty grid!");
-        assert_ne!(width, 0, "Empty grid!");
-        Self {
+        let width = width.unwrap_or(0);
+        if height == 0 || width == 0 {
+            return Err(GridError::Empty);
+        }
+        Ok(Self {
             data,
             width,
             height,
+        })
+    }
+
+    /// Reads labelled pictures such as `0:` followed by a `###`/`##.` block,
+    /// mapping cells with `f` like [`Grid::from_str`]. Blocks come first and may
+    /// be followed by one section without a label, which is returned as is.
+    /// # Examples
+    /// ```
+    /// use std::collections::HashMap;
+    /// use utils::grid::Grid;
+    ///
+    /// let input = "0:\n##\n#.\n\n1:\n#\n\n4x4: 1 0\n5x5: 0 2\n";
+    /// let (shapes, rest) = Grid::labelled_blocks(input, |c| c == '#').unwrap();
+    /// assert_eq!(shapes.len(), 2);
+    /// assert_eq!((shapes[0].0, shapes[0].1.size()), ("0", (2, 2)));
+    /// assert_eq!(rest.unwrap().line, 8);
+    ///
+    /// let shapes = shapes.into_iter().collect::<HashMap<_, _>>();
+    /// assert_eq!(shapes["1"].size(), (1, 1));
+    ///
+    /// let err = Grid::labelled_blocks("0:\n##\n#\n", |c| c == '#').unwrap_err();
+    /// assert_eq!((err.line, err.column), (3, 2));
+    /// let err = Grid::labelled_blocks("0:\n#\n\n1x1: 1\n\n2x2: 0\n", |c| c == '#').unwrap_err();
+    /// assert_eq!((err.line, err.expected.as_str()), (6, "end of input after the unlabelled section"));
+    /// ```
+    pub fn labelled_blocks<'a>(
+        input: &'a str,
+        f: impl Fn(char) -> T,
+    ) -> ParseResult<LabelledBlocks<'a, T>> {
+        let mut blocks = vec![];
+        let mut sections = sections(input);
+        for section in sections.by_ref() {
+            match section.header() {
+                Some((label, picture)) => {
+                    let grid = Self::try_from_str(picture.text, |c| Some(f(c)))
+                        .map_err(|err| err.in_section(&picture))?;
+                    blocks.push((label, grid));
+                }
+                None => {
+                    if let Some(extra) = sections.next() {
+                        let first = extra.lines().next().unwrap_or_default();
+                        return Err(ParseError::at(
+                            extra.line,
+                            first,
+                            0,
+                            "end of input after the unlabelled section",
+                        ));
+                    }
+                    return Ok((blocks, Some(section)));
+                }
+            }
         }
+        Ok((blocks, None))
     }
 
     pub fn blank(height: usize, width: usize, def: T) -> Self {
@@ -93,35 +391,36 @@ impl<T: Debug + Clone> Grid<T> {
         }
     }
 
+    /// Panics on ragged or empty input, see [`Grid::try_from_2d_vec`].
     pub fn from_2d_vec(data: Vec<Vec<T>>) -> Self {
-        let height = data.len();
-        assert_ne!(height, 0, "Empty grid!");
-        let width = data[0].len();
-        assert_ne!(width, 0, "Empty grid!");
-        let mut flat_data = Vec::with_capacity(height * width);
-        for row in data {
-            assert_eq!(row.len(), width, "Inconsistent row length!");
-            flat_data.extend(row);
-        }
-        Self {
-            data: flat_data,
+        Self::try_from_2d_vec(data).unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    /// # Examples
+    /// ```
+    /// use utils::grid::{Grid, GridError};
+    ///
+    /// assert_eq!(Grid::try_from_2d_vec(vec![vec![1, 2], vec![3, 4]]).unwrap()[(1usize, 0usize)], 3);
+    /// let err = Grid::try_from_2d_vec(vec![vec![1, 2], vec![3]]).unwrap_err();
+    /// assert_eq!(err, GridError::Ragged { row: 1, column: 1, width: 2, found: None });
+    /// assert_eq!(Grid::<u8>::try_from_2d_vec(vec![vec![]]).unwrap_err(), GridError::Empty);
+    /// assert_eq!(Grid::try_from_2d_vec(vec![vec![], vec![1]]).unwrap_err(), GridError::Empty);
+    /// ```
+    pub fn try_from_2d_vec(data: Vec<Vec<T>>) -> Result<Self, GridError> {
+        let (height, width) = check_rows(data.iter().map(Vec::len))?;
+        Ok(Self {
+            data: data.into_iter().flatten().collect(),
             width,
             height,
-        }
+        })
     }
 
+    /// Panics on ragged or empty input, like [`Grid::from_2d_vec`].
     pub fn from_2d_slice(data: &[&[T]]) -> Self {
-        let height = data.len();
-        assert_ne!(height, 0, "Empty grid!");
-        let width = data[0].len();
-        assert_ne!(width, 0, "Empty grid!");
-        let mut flat_data = Vec::with_capacity(height * width);
-        for row in data {
-            assert_eq!(row.len(), width, "Inconsistent row length!");
-            flat_data.extend_from_slice(row);
-        }
+        let (height, width) =
+            check_rows(data.iter().map(|row| row.len())).unwrap_or_else(|err| panic!("{}", err));
         Self {
-            data: flat_data,
+            data: data.concat(),
             width,
             height,
         }
@@ -159,9 +458,121 @@ impl<T: Debug + Clone> Grid<T> {
         (self.height, self.width)
     }
 
-    pub fn sub_grid(&self, height: usize, width: usize) -> SubGrid<T> {
-        SubGrid {
-            grid: self,
+    /// Borrows the `height` by `width` window whose top left cell is `origin`,
+    /// or `None` if it does not fit in the grid.
+    /// # Examples
+    /// ```
+    /// use utils::grid::Grid;
+    ///
+    /// let grid = Grid::char_grid("abcd\nefgh\nijkl");
+    /// let sub = grid.sub_grid((1, 1), 2, 2).unwrap();
+    /// assert_eq!((sub[(0, 0)], sub[(1, 1)], sub.origin()), ('f', 'k', (1, 1)));
+    /// assert_eq!(sub.to_string(), "fg\njk\n");
+    /// assert_eq!(sub.to_grid().size(), (2, 2));
+    /// assert!(sub.get((2, 0)).is_none());
+    /// assert!(grid.sub_grid((2, 3), 2, 1).is_none());
+    /// ```
+    pub fn sub_grid(
+        &self,
+        origin: Position,
+        height: usize,
+        width: usize,
+    ) -> Option<SubGrid<'_, T>> {
+        (height > 0
+            && width > 0
+            && origin.0 + height <= self.height
+            && origin.1 + width <= self.width)
+            .then_some(SubGrid {
+                grid: self,
+                origin,
+                height,
+                width,
+            })
+    }
+
+    /// Every `height` by `width` window, row by row, overlapping like [`slice::windows`].
+    /// # Examples
+    /// ```
+    /// use utils::grid::Grid;
+    ///
+    /// let grid = Grid::char_grid("#.#\n.#.\n#.#");
+    /// let pattern = Grid::char_grid("#.\n.#");
+    /// let found = grid.windows(2, 2).filter(|w| w.to_string() == pattern.to_string());
+    /// assert_eq!(found.map(|w| w.origin()).collect::<Vec<_>>(), [(0, 0), (1, 1)]);
+    /// assert_eq!(grid.windows(4, 1).count(), 0);
+    /// ```
+    pub fn windows(&self, height: usize, width: usize) -> SubGrids<'_, T> {
+        SubGrids::new(self, height, width, false)
+    }
+
+    /// Tiles the grid with `height` by `width` blocks, row by row. Blocks at
+    /// the bottom and right edge are smaller if the size does not divide evenly.
+    /// # Examples
+    /// ```
+    /// use utils::grid::Grid;
+    ///
+    /// let grid = Grid::char_grid("abc\ndef\nghi");
+    /// let sizes = grid.chunks(2, 2).map(|c| (c.origin(), c.size())).collect::<Vec<_>>();
+    /// assert_eq!(sizes, [((0, 0), (2, 2)), ((0, 2), (2, 1)), ((2, 0), (1, 2)), ((2, 2), (1, 1))]);
+    /// assert_eq!(grid.chunks(5, 5).map(|c| c.size()).collect::<Vec<_>>(), [(3, 3)]);
+    /// ```
+    pub fn chunks(&self, height: usize, width: usize) -> SubGrids<'_, T> {
+        SubGrids::new(self, height, width, true)
+    }
+
+    /// Splits the grid at columns whose cells all match `is_separator`,
+    /// dropping the separators. Blocks keep their offset in this grid.
+    /// # Examples
+    /// ```
+    /// use utils::grid::Grid;
+    ///
+    /// let grid = Grid::char_grid("12 4\n3  5\n+  *");
+    /// let blocks = grid.split_cols(|&c| c == ' ');
+    /// assert_eq!(blocks.len(), 2);
+    /// assert_eq!((blocks[1].offset, blocks[1].grid.size()), ((0, 3), (3, 1)));
+    /// assert_eq!(blocks[0].grid.row_numbers::<u32>().unwrap(), [12, 3]);
+    /// assert_eq!(blocks[0].grid.col_numbers::<u32>().unwrap(), [13, 2]);
+    ///
+    /// let err = Grid::char_grid("  1\n999").row_numbers::<u8>().unwrap_err();
+    /// assert_eq!((err.line, err.column), (2, 1));
+    /// let err = Grid::char_grid(" 3\n 9\n 9").col_numbers::<u8>().unwrap_err();
+    /// assert_eq!((err.line, err.column, err.snippet.as_str()), (1, 2, "3"));
+    /// assert!(err.to_string().starts_with("line 1, column 2: expected a column number that fits in u8"));
+    /// ```
+    pub fn split_cols(&self, is_separator: impl Fn(&T) -> bool) -> Vec<Block<T>> {
+        separated_runs(self.width, |x| {
+            (0..self.height).all(|y| is_separator(&self[(y, x)]))
+        })
+        .into_iter()
+        .map(|(start, end)| Block {
+            offset: (0, start),
+            grid: self.cut(0..self.height, start..end),
+        })
+        .collect()
+    }
+
+    /// Splits the grid at rows whose cells all match `is_separator`, see [`Grid::split_cols`].
+    pub fn split_rows(&self, is_separator: impl Fn(&T) -> bool) -> Vec<Block<T>> {
+        separated_runs(self.height, |y| {
+            self.get_row(y).unwrap().iter().all(&is_separator)
+        })
+        .into_iter()
+        .map(|(start, end)| Block {
+            offset: (start, 0),
+            grid: self.cut(start..end, 0..self.width),
+        })
+        .collect()
+    }
+
+    /// Copies the cells in the given rows and columns into a new grid.
+    fn cut(&self, rows: Range<usize>, cols: Range<usize>) -> Self {
+        let width = cols.len();
+        let height = rows.len();
+        let data = rows
+            .flat_map(|y| self.get_row(y).unwrap()[cols.clone()].iter().cloned())
+            .collect();
+        Self {
+            data,
             height,
             width,
         }
@@ -216,7 +627,7 @@ impl<T: Debug + Clone> Grid<T> {
         positions
             .iter()
             .filter(|&pos| self.validate_position(*pos))
-            .map(|&pos| pos)
+            .copied()
             .collect()
     }
 
@@ -236,16 +647,34 @@ impl<T: Debug + Clone> Grid<T> {
         }
     }
 
-    pub fn get_col(&self, x: usize) -> Option<Vec<T>> {
-        if x < self.width {
-            Some(
-                (0..self.height)
-                    .map(|y| self.data[y * self.width + x].clone())
-                    .collect(),
-            )
-        } else {
-            None
-        }
+    /// Borrows column `x` without copying it.
+    /// # Examples
+    /// ```
+    /// use utils::grid::Grid;
+    ///
+    /// let mut grid = Grid::char_grid("abc\ndef");
+    /// let col = grid.get_col(1).unwrap();
+    /// assert_eq!((col.len(), col[1]), (2, 'e'));
+    /// assert_eq!(col.iter().rev().collect::<String>(), "eb");
+    /// assert!(grid.get_col(3).is_none());
+    ///
+    /// grid.get_col_mut(2).unwrap().swap(0, 1);
+    /// assert_eq!(grid.iter_cols().map(|col| col.to_vec()).last().unwrap(), ['f', 'c']);
+    /// ```
+    pub fn get_col(&self, x: usize) -> Option<ColumnView<'_, T>> {
+        (x < self.width).then(|| ColumnView {
+            data: &self.data,
+            width: self.width,
+            x,
+        })
+    }
+
+    pub fn get_col_mut(&mut self, x: usize) -> Option<ColumnViewMut<'_, T>> {
+        (x < self.width).then(|| ColumnViewMut {
+            data: &mut self.data,
+            width: self.width,
+            x,
+        })
     }
 
     pub fn iter(&self) -> GridIter<'_, T> {
@@ -417,6 +846,26 @@ impl<T: Debug + Clone> Grid<T> {
         y1.abs_diff(*y2) + x1.abs_diff(*x2)
     }
 
+    /// Chebyshev distance, the number of steps when diagonal moves are allowed.
+    pub fn distance_ordinal<P: Borrow<Position>, Q: Borrow<Position>>(
+        &self,
+        pos_a: P,
+        pos_b: Q,
+    ) -> usize {
+        let (y1, x1) = pos_a.borrow();
+        let (y2, x2) = pos_b.borrow();
+        y1.abs_diff(*y2).max(x1.abs_diff(*x2))
+    }
+
+    /// Lower bound on the steps between two positions when moving in the directions `D`.
+    pub fn distance<D: Direction + 'static>(&self, pos_a: Position, pos_b: Position) -> usize {
+        if D::has_diagonals() {
+            self.distance_ordinal(pos_a, pos_b)
+        } else {
+            self.distance_cardinal(pos_a, pos_b)
+        }
+    }
+
     pub fn areas<F: Fn(&T, &T) -> bool>(
         &self,
         is_part_of_same_area: F,
@@ -457,6 +906,77 @@ impl Grid<char> {
     pub fn char_grid(input: &str) -> Self {
         Self::from_str(input, |c| c)
     }
+
+    /// Reads every row as a number from its digits, ignoring anything else.
+    /// Rows without digits are skipped. A number that does not fit in `I` is an
+    /// error at the row and column of its first digit, with the row as the line.
+    pub fn row_numbers<I: Int>(&self) -> ParseResult<Vec<I>> {
+        self.iter_rows()
+            .enumerate()
+            .filter_map(|(y, row)| {
+                let number = digits_to_number(row)?;
+                Some(number.map_err(|x| self.number_error::<I>((y, x), "row")))
+            })
+            .collect()
+    }
+
+    /// Reads every column top to bottom as a number, see [`Grid::row_numbers`].
+    pub fn col_numbers<I: Int>(&self) -> ParseResult<Vec<I>> {
+        self.iter_cols()
+            .enumerate()
+            .filter_map(|(x, col)| {
+                let number = digits_to_number(col)?;
+                Some(number.map_err(|y| self.number_error::<I>((y, x), "column")))
+            })
+            .collect()
+    }
+
+    /// Error for a `kind` number starting at `pos` that does not fit in `I`.
+    /// The snippet is the cell itself, since a column number runs across rows.
+    fn number_error<I: Int>(&self, (y, x): Position, kind: &str) -> ParseError {
+        let source_line = self.iter_rows().nth(y).unwrap().iter().collect::<String>();
+        let start = source_line.char_indices().nth(x).unwrap().0;
+        let expected = format!(
+            "a {} number that fits in {}",
+            kind,
+            std::any::type_name::<I>()
+        );
+        ParseError {
+            snippet: self[(y, x)].to_string(),
+            ..ParseError::at(y + 1, &source_line, start, expected)
+        }
+    }
+}
+
+/// The number formed by the digits among `cells`, `None` without digits, or
+/// the index of the first digit if it does not fit in `I`.
+fn digits_to_number<'a, I: Int>(
+    cells: impl IntoIterator<Item = &'a char> + Copy,
+) -> Option<Result<I, usize>> {
+    let first = cells.into_iter().position(|c| c.is_ascii_digit())?;
+    let number = cells
+        .into_iter()
+        .filter_map(|c| c.to_digit(10))
+        .try_fold(0u128, |acc, d| acc.checked_mul(10)?.checked_add(d as u128))
+        .and_then(|magnitude| I::from_magnitude(false, magnitude));
+    Some(number.ok_or(first))
+}
+
+/// Start and end of every run of indices in `0..len` that are not separators.
+fn separated_runs(len: usize, is_separator: impl Fn(usize) -> bool) -> Vec<(usize, usize)> {
+    let mut runs = vec![];
+    let mut start = None;
+    for i in 0..=len {
+        match (start, i == len || is_separator(i)) {
+            (Some(s), true) => {
+                runs.push((s, i));
+                start = None;
+            }
+            (None, false) => start = Some(i),
+            _ => {}
+        }
+    }
+    runs
 }
 
 impl<T: Debug + Clone + Default> Grid<T> {
@@ -552,22 +1072,149 @@ impl<'a, T: Debug + Clone> Iterator for RowIter<'a, T> {
 
 pub struct ColIter<'a, T: Debug + Clone> {
     grid: &'a Grid<T>,
-    i: usize,
+    cols: Range<usize>,
 }
 
 impl<'a, T: Debug + Clone> ColIter<'a, T> {
     pub fn new(grid: &'a Grid<T>) -> Self {
-        Self { grid, i: 0 }
+        Self {
+            grid,
+            cols: 0..grid.width,
+        }
     }
 }
 
 impl<'a, T: Debug + Clone> Iterator for ColIter<'a, T> {
-    type Item = Vec<T>; // TODO: &[T]
+    type Item = ColumnView<'a, T>;
 
     fn next(&mut self) -> Option<Self::Item> {
-        let next = self.grid.get_col(self.i);
-        self.i += 1;
-        next
+        self.grid.get_col(self.cols.next()?)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.cols.size_hint()
+    }
+}
+
+impl<T: Debug + Clone> DoubleEndedIterator for ColIter<'_, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.grid.get_col(self.cols.next_back()?)
+    }
+}
+
+impl<T: Debug + Clone> ExactSizeIterator for ColIter<'_, T> {}
+
+/// A grid column, borrowed from the grid's row-major data with a stride.
+#[derive(Debug, Clone, Copy)]
+pub struct ColumnView<'a, T> {
+    data: &'a [T],
+    width: usize,
+    x: usize,
+}
+
+impl<'a, T> ColumnView<'a, T> {
+    /// Index of the column within the grid.
+    pub fn x(&self) -> usize {
+        self.x
+    }
+
+    pub fn len(&self) -> usize {
+        self.data.len() / self.width
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+
+    pub fn get(&self, y: usize) -> Option<&'a T> {
+        self.data.get(y * self.width + self.x)
+    }
+
+    /// Cells from top to bottom; use `.rev()` for bottom to top.
+    pub fn iter(&self) -> std::iter::StepBy<std::slice::Iter<'a, T>> {
+        self.data[self.x..].iter().step_by(self.width)
+    }
+
+    pub fn to_vec(&self) -> Vec<T>
+    where
+        T: Clone,
+    {
+        self.iter().cloned().collect()
+    }
+}
+
+impl<T> Index<usize> for ColumnView<'_, T> {
+    type Output = T;
+
+    fn index(&self, y: usize) -> &T {
+        &self.data[y * self.width + self.x]
+    }
+}
+
+impl<'a, T> IntoIterator for ColumnView<'a, T> {
+    type Item = &'a T;
+    type IntoIter = std::iter::StepBy<std::slice::Iter<'a, T>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// Mutable counterpart of [`ColumnView`], see [`Grid::get_col_mut`].
+#[derive(Debug)]
+pub struct ColumnViewMut<'a, T> {
+    data: &'a mut [T],
+    width: usize,
+    x: usize,
+}
+
+impl<T> ColumnViewMut<'_, T> {
+    pub fn x(&self) -> usize {
+        self.x
+    }
+
+    pub fn len(&self) -> usize {
+        self.data.len() / self.width
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+
+    pub fn get(&self, y: usize) -> Option<&T> {
+        self.data.get(y * self.width + self.x)
+    }
+
+    pub fn get_mut(&mut self, y: usize) -> Option<&mut T> {
+        self.data.get_mut(y * self.width + self.x)
+    }
+
+    pub fn iter(&self) -> std::iter::StepBy<std::slice::Iter<'_, T>> {
+        self.data[self.x..].iter().step_by(self.width)
+    }
+
+    pub fn iter_mut(&mut self) -> std::iter::StepBy<std::slice::IterMut<'_, T>> {
+        self.data[self.x..].iter_mut().step_by(self.width)
+    }
+
+    /// Swaps the cells in rows `a` and `b`.
+    pub fn swap(&mut self, a: usize, b: usize) {
+        self.data
+            .swap(a * self.width + self.x, b * self.width + self.x);
+    }
+}
+
+impl<T> Index<usize> for ColumnViewMut<'_, T> {
+    type Output = T;
+
+    fn index(&self, y: usize) -> &T {
+        &self.data[y * self.width + self.x]
+    }
+}
+
+impl<T> IndexMut<usize> for ColumnViewMut<'_, T> {
+    fn index_mut(&mut self, y: usize) -> &mut T {
+        &mut self.data[y * self.width + self.x]
     }
 }
 
@@ -604,13 +1251,155 @@ impl<'a, 'b, T: Debug + Clone, D: Direction + 'static> Iterator for NeighborIter
     }
 }
 
-#[allow(dead_code)]
+/// A part of a grid cut out by [`Grid::split_cols`] or [`Grid::split_rows`].
+#[derive(Debug, Clone)]
+pub struct Block<T: Debug + Clone> {
+    /// Position of the block's top left cell in the original grid.
+    pub offset: Position,
+    pub grid: Grid<T>,
+}
+
+/// A borrowed window into a grid, indexed relative to its top left cell.
+#[derive(Debug, Clone, Copy)]
 pub struct SubGrid<'a, T: Debug + Clone> {
     grid: &'a Grid<T>,
+    origin: Position,
     height: usize,
     width: usize,
 }
 
+impl<T: Debug + Clone + Display> Display for SubGrid<'_, T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for row in self.iter_rows() {
+            for cell in row {
+                write!(f, "{}", cell)?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+impl<T: Debug + Clone> Index<Position> for SubGrid<'_, T> {
+    type Output = T;
+
+    fn index(&self, (y, x): Position) -> &Self::Output {
+        assert!(
+            y < self.height && x < self.width,
+            "Position outside of sub-grid!"
+        );
+        &self.grid[(self.origin.0 + y, self.origin.1 + x)]
+    }
+}
+
+impl<'a, T: Debug + Clone> SubGrid<'a, T> {
+    /// Position of the top left cell in the whole grid.
+    pub fn origin(&self) -> Position {
+        self.origin
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn size(&self) -> (usize, usize) {
+        (self.height, self.width)
+    }
+
+    pub fn get(&self, (y, x): Position) -> Option<&'a T> {
+        if y < self.height && x < self.width {
+            self.grid.get((self.origin.0 + y, self.origin.1 + x))
+        } else {
+            None
+        }
+    }
+
+    /// Converts a local position into one in the whole grid.
+    pub fn to_global(&self, (y, x): Position) -> Position {
+        (self.origin.0 + y, self.origin.1 + x)
+    }
+
+    pub fn iter_rows(&self) -> impl DoubleEndedIterator<Item = &'a [T]> + '_ {
+        (0..self.height).map(|y| {
+            let row = self.grid.get_row(self.origin.0 + y).unwrap();
+            &row[self.origin.1..self.origin.1 + self.width]
+        })
+    }
+
+    /// Cells with their local positions, row by row.
+    pub fn iter(&self) -> impl Iterator<Item = (Position, &'a T)> + '_ {
+        self.iter_rows()
+            .enumerate()
+            .flat_map(|(y, row)| row.iter().enumerate().map(move |(x, t)| ((y, x), t)))
+    }
+
+    pub fn to_grid(&self) -> Grid<T> {
+        Grid {
+            data: self.iter_rows().flatten().cloned().collect(),
+            height: self.height,
+            width: self.width,
+        }
+    }
+}
+
+/// Iterator returned by [`Grid::windows`] and [`Grid::chunks`].
+#[derive(Debug, Clone)]
+pub struct SubGrids<'a, T: Debug + Clone> {
+    grid: &'a Grid<T>,
+    height: usize,
+    width: usize,
+    /// Tile without overlap and clip at the edges, instead of sliding by one.
+    chunks: bool,
+    next: Option<Position>,
+}
+
+impl<'a, T: Debug + Clone> SubGrids<'a, T> {
+    fn new(grid: &'a Grid<T>, height: usize, width: usize, chunks: bool) -> Self {
+        assert!(height > 0 && width > 0, "Empty sub-grid size!");
+        let fits = chunks || (height <= grid.height && width <= grid.width);
+        Self {
+            grid,
+            height,
+            width,
+            chunks,
+            next: fits.then_some((0, 0)),
+        }
+    }
+
+    /// The next start after `i` along an axis of length `len`, if any.
+    fn advance(&self, i: usize, size: usize, len: usize) -> Option<usize> {
+        if self.chunks {
+            Some(i + size).filter(|&next| next < len)
+        } else {
+            Some(i + 1).filter(|&next| next + size <= len)
+        }
+    }
+}
+
+impl<'a, T: Debug + Clone> Iterator for SubGrids<'a, T> {
+    type Item = SubGrid<'a, T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (y, x) = self.next?;
+        self.next = match self.advance(x, self.width, self.grid.width) {
+            Some(x) => Some((y, x)),
+            None => self
+                .advance(y, self.height, self.grid.height)
+                .map(|y| (y, 0)),
+        };
+        Some(SubGrid {
+            grid: self.grid,
+            origin: (y, x),
+            height: self.height.min(self.grid.height - y),
+            width: self.width.min(self.grid.width - x),
+        })
+    }
+}
+
 pub struct GridPos<I: Integer>(pub I, pub I);
 
 impl<I: Integer> GridPos<I> {
@@ -620,6 +1409,7 @@ impl<I: Integer> GridPos<I> {
 }
 
 impl<I: Integer + std::str::FromStr> GridPos<I> {
+    #[allow(clippy::should_implement_trait)]
     pub fn from_str(input: &str) -> Result<Self, I::Err> {
         let mut parts = input.split(',');
         let y = parts.next().unwrap().parse()?;
@@ -763,6 +1553,27 @@ impl<T: Debug + Clone + Obstructs> Grid<T> {
             .filter(|(_, t)| !t.obstructs())
     }
 
+    /// Shortest path from `start` to `end` moving in the directions `D`, with its length.
+    /// The heuristic is the Chebyshev distance when `D` has diagonals, since the
+    /// Manhattan distance overestimates there and A* could settle for a longer path.
+    /// # Examples
+    /// ```
+    /// use utils::grid::{Grid, Obstructs};
+    ///
+    /// #[derive(Debug, Clone)]
+    /// struct Wall(bool);
+    ///
+    /// impl Obstructs for Wall {
+    ///     fn obstructs(&self) -> bool {
+    ///         self.0
+    ///     }
+    /// }
+    ///
+    /// // With the Manhattan heuristic, this returned a path of 6 steps.
+    /// let grid = Grid::from_str("...#..\n..#.#.\n..##..\n....#.", |c| Wall(c == '#'));
+    /// assert_eq!(grid.astar_ordinal(&(0, 0), &(3, 5)).unwrap().1, 5);
+    /// assert_eq!(grid.astar_cardinal(&(0, 0), &(3, 5)), None);
+    /// ```
     pub fn astar<D: Direction + 'static>(
         &self,
         start: &Position,
@@ -775,7 +1586,7 @@ impl<T: Debug + Clone + Obstructs> Grid<T> {
                     .map(|(n, _)| (n, 1))
                     .collect::<Vec<_>>()
             },
-            |pos| self.distance_cardinal(*pos, *end),
+            |pos| self.distance::<D>(*pos, *end),
             |pos| pos == end,
         )
     }
@@ -808,7 +1619,7 @@ impl<T: Debug + Clone + Obstructs> Grid<T> {
                     .map(|(n, _)| (n, 1))
                     .collect::<Vec<_>>()
             },
-            |pos| self.distance_cardinal(*pos, *end),
+            |pos| self.distance::<D>(*pos, *end),
             |pos| pos == end,
         )
     }
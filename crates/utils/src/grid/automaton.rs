@@ -0,0 +1,203 @@
+use std::collections::VecDeque;
+use std::fmt::Debug;
+use std::marker::PhantomData;
+
+use super::{Grid, Position};
+use crate::direction::Direction;
+
+/// The neighbors of a cell in the directions of `D`, as seen by a rule.
+/// Neighbors outside of the grid are left out.
+pub struct Neighborhood<'a, T: Debug + Clone, D> {
+    grid: &'a Grid<T>,
+    pos: Position,
+    _direction: PhantomData<D>,
+}
+
+impl<'a, T: Debug + Clone, D: Direction + 'static> Neighborhood<'a, T, D> {
+    pub fn pos(&self) -> Position {
+        self.pos
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &'a T> + '_ {
+        D::all()
+            .iter()
+            .filter_map(|d| self.grid.get_virtual(d.add_unsigned(&self.pos, 1)))
+    }
+
+    /// How many neighbors equal `value`.
+    pub fn count(&self, value: &T) -> usize
+    where
+        T: PartialEq,
+    {
+        self.iter().filter(|t| *t == value).count()
+    }
+
+    pub fn count_where(&self, f: impl Fn(&T) -> bool) -> usize {
+        self.iter().filter(|t| f(t)).count()
+    }
+}
+
+/// What a run of an [`Automaton`] did.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Report {
+    /// Generations that changed the grid. In worklist mode, the number of
+    /// cell updates that changed a cell; checks that keep a cell as it is do not count.
+    pub steps: usize,
+    /// Cells that changed at least once.
+    pub changed: usize,
+    /// The step after which nothing changed anymore, `None` if
+    /// [`Automaton::max_steps`] stopped the run while cells were still changing.
+    pub fixed_point: Option<usize>,
+}
+
+/// Applies a rule to every cell of a grid until nothing changes. The rule gets
+/// a cell and its neighbors in the directions of `D`, and returns the cell's
+/// new value.
+/// # Examples
+/// ```
+/// use utils::direction::OrdinalDirection;
+/// use utils::grid::automaton::{Automaton, Neighborhood};
+/// use utils::grid::Grid;
+///
+/// // Remove every `@` with fewer than 4 `@` neighbors, until none is left.
+/// fn rule(&c: &char, n: &Neighborhood<char, OrdinalDirection>) -> char {
+///     if c == '@' && n.count(&'@') < 4 { '.' } else { c }
+/// }
+///
+/// let mut grid = Grid::char_grid("@@@.\n@@@@\n@@@.\n.@..");
+/// let report = Automaton::<OrdinalDirection>::new().worklist(&mut grid, rule);
+/// assert_eq!((report.steps, report.changed, report.fixed_point), (11, 11, Some(11)));
+/// assert_eq!(grid.to_string(), "....\n....\n....\n....\n");
+///
+/// let mut grid = Grid::char_grid("@@@.\n@@@@\n@@@.\n.@..");
+/// let report = Automaton::<OrdinalDirection>::new().generations(&mut grid, rule);
+/// assert_eq!((report.steps, report.changed, report.fixed_point), (4, 11, Some(4)));
+///
+/// let mut grid = Grid::char_grid("@@@.\n@@@@\n@@@.\n.@..");
+/// let report = Automaton::<OrdinalDirection>::new().max_steps(1).generations(&mut grid, rule);
+/// assert_eq!((report.steps, report.fixed_point), (1, None));
+///
+/// let mut grid = Grid::char_grid("@@@.\n@@@@\n@@@.\n.@..");
+/// let report = Automaton::<OrdinalDirection>::new().max_steps(4).generations(&mut grid, rule);
+/// assert_eq!((report.steps, report.fixed_point), (4, Some(4)));
+///
+/// let mut grid = Grid::char_grid("@@@.\n@@@@\n@@@.\n.@..");
+/// let report = Automaton::<OrdinalDirection>::new().max_steps(11).worklist(&mut grid, rule);
+/// assert_eq!((report.steps, report.fixed_point), (11, Some(11)));
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct Automaton<D> {
+    max_steps: Option<usize>,
+    _direction: PhantomData<D>,
+}
+
+impl<D: Direction + 'static> Default for Automaton<D> {
+    fn default() -> Self {
+        Self {
+            max_steps: None,
+            _direction: PhantomData,
+        }
+    }
+}
+
+impl<D: Direction + 'static> Automaton<D> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Stops after `steps` generations or cell updates, even without a fixed point.
+    /// The grid is still checked once more, so reaching a fixed point exactly
+    /// at the limit is reported as such.
+    pub fn max_steps(self, steps: usize) -> Self {
+        Self {
+            max_steps: Some(steps),
+            ..self
+        }
+    }
+
+    fn neighborhood<'a, T: Debug + Clone>(
+        grid: &'a Grid<T>,
+        pos: Position,
+    ) -> Neighborhood<'a, T, D> {
+        Neighborhood {
+            grid,
+            pos,
+            _direction: PhantomData,
+        }
+    }
+
+    /// Updates all cells at once, every generation seeing only the previous one.
+    pub fn generations<T: Debug + Clone + PartialEq>(
+        &self,
+        grid: &mut Grid<T>,
+        rule: impl Fn(&T, &Neighborhood<'_, T, D>) -> T,
+    ) -> Report {
+        let mut report = Report::default();
+        let mut touched = vec![false; grid.data.len()];
+        loop {
+            let mut changed = false;
+            let next = grid
+                .iter()
+                .enumerate()
+                .map(|(i, (pos, cell))| {
+                    let new = rule(cell, &Self::neighborhood(grid, pos));
+                    if new != *cell {
+                        changed = true;
+                        touched[i] = true;
+                    }
+                    new
+                })
+                .collect();
+            if !changed {
+                report.fixed_point = Some(report.steps);
+                break;
+            }
+            if self.max_steps.is_some_and(|max| report.steps >= max) {
+                break;
+            }
+            grid.data = next;
+            report.steps += 1;
+        }
+        report.changed = touched.iter().filter(|&&t| t).count();
+        report
+    }
+
+    /// Updates one cell at a time, in place. Every cell is checked once, after
+    /// which only the neighbors of changed cells are checked again.
+    pub fn worklist<T: Debug + Clone + PartialEq>(
+        &self,
+        grid: &mut Grid<T>,
+        rule: impl Fn(&T, &Neighborhood<'_, T, D>) -> T,
+    ) -> Report {
+        let mut report = Report::default();
+        let mut touched = vec![false; grid.data.len()];
+        let mut queued = vec![true; grid.data.len()];
+        let mut queue = grid.iter_positions().collect::<VecDeque<_>>();
+        while let Some(pos) = queue.pop_front() {
+            let i = pos.0 * grid.width + pos.1;
+            queued[i] = false;
+            let new = rule(&grid[pos], &Self::neighborhood(grid, pos));
+            if new == grid[pos] {
+                continue;
+            }
+            if self.max_steps.is_some_and(|max| report.steps >= max) {
+                queue.push_front(pos);
+                break;
+            }
+            grid[pos] = new;
+            touched[i] = true;
+            report.steps += 1;
+            for next in std::iter::once(pos).chain(grid.neighbors::<D>(pos)) {
+                let i = next.0 * grid.width + next.1;
+                if !std::mem::replace(&mut queued[i], true) {
+                    queue.push_back(next);
+                }
+            }
+        }
+        if queue.is_empty() {
+            report.fixed_point = Some(report.steps);
+        }
+        report.changed = touched.iter().filter(|&&t| t).count();
+        report
+    }
+}
@@ -0,0 +1,349 @@
+use std::fmt::{Debug, Display};
+use std::ops::{BitAnd, BitOr, BitXor, Index, Not};
+
+use super::{Grid, Position};
+use crate::direction::{CardinalDirection, Direction, OrdinalDirection, PositionVirtual};
+
+/// Grid of booleans packed 64 to a `u64`, row by row. Besides the usual
+/// position API, whole grids can be shifted and combined a word at a time,
+/// which makes neighbor counting cheap.
+/// # Examples
+/// ```
+/// use utils::direction::OrdinalDirection;
+/// use utils::grid::BitGrid;
+///
+/// let paper = BitGrid::from_str("@@.\n@@@\n.@.", |c| c == '@');
+/// assert_eq!((paper.count_ones(), paper[(1, 2)], paper[(2, 2)]), (6, true, false));
+///
+/// let crowded = BitGrid::at_least(&paper.neighbor_masks::<OrdinalDirection>(), 4);
+/// let accessible = &paper & &!&crowded;
+/// assert_eq!(accessible.to_string(), "#..\n..#\n.#.\n");
+/// assert_eq!(accessible.ones().collect::<Vec<_>>(), [(0, 0), (1, 2), (2, 1)]);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct BitGrid {
+    words: Vec<u64>,
+    height: usize,
+    width: usize,
+    /// Words per row. Bits past `width` in a row's last word are always 0.
+    stride: usize,
+}
+
+impl Display for BitGrid {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for y in 0..self.height {
+            for x in 0..self.width {
+                write!(f, "{}", if self[(y, x)] { '#' } else { '.' })?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+impl Index<Position> for BitGrid {
+    type Output = bool;
+
+    fn index(&self, pos: Position) -> &bool {
+        match self.get(pos) {
+            Some(true) => &true,
+            Some(false) => &false,
+            None => panic!("Position {:?} outside of grid!", pos),
+        }
+    }
+}
+
+impl BitGrid {
+    pub fn new(height: usize, width: usize) -> Self {
+        let stride = width.div_ceil(64);
+        Self {
+            words: vec![0; height * stride],
+            height,
+            width,
+            stride,
+        }
+    }
+
+    /// Reads a grid like [`Grid::from_str`], setting the cells where `f` is true.
+    pub fn from_str(input: &str, f: impl Fn(char) -> bool) -> Self {
+        Self::from_grid(&Grid::from_str(input, f), |&b| b)
+    }
+
+    pub fn from_grid<T: Debug + Clone>(grid: &Grid<T>, f: impl Fn(&T) -> bool) -> Self {
+        let mut bits = Self::new(grid.height, grid.width);
+        for ((y, x), cell) in grid.iter() {
+            if f(cell) {
+                bits.set((y, x), true);
+            }
+        }
+        bits
+    }
+
+    pub fn to_grid(&self) -> Grid<bool> {
+        Grid {
+            data: self.iter().map(|(_, b)| b).collect(),
+            height: self.height,
+            width: self.width,
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn size(&self) -> (usize, usize) {
+        (self.height, self.width)
+    }
+
+    pub fn validate_position(&self, (y, x): Position) -> bool {
+        y < self.height && x < self.width
+    }
+
+    pub fn validate_position_virtual(&self, (y, x): PositionVirtual) -> bool {
+        y >= 0 && x >= 0 && self.validate_position((y as usize, x as usize))
+    }
+
+    /// Word index and bit mask of a position.
+    fn locate(&self, (y, x): Position) -> (usize, u64) {
+        (y * self.stride + x / 64, 1 << (x % 64))
+    }
+
+    pub fn get(&self, pos: Position) -> Option<bool> {
+        self.validate_position(pos).then(|| {
+            let (word, mask) = self.locate(pos);
+            self.words[word] & mask != 0
+        })
+    }
+
+    pub fn get_virtual(&self, pos: PositionVirtual) -> Option<bool> {
+        if self.validate_position_virtual(pos) {
+            self.get((pos.0 as usize, pos.1 as usize))
+        } else {
+            None
+        }
+    }
+
+    /// Panics if `pos` is outside of the grid.
+    pub fn set(&mut self, pos: Position, value: bool) {
+        assert!(
+            self.validate_position(pos),
+            "Position {:?} outside of grid!",
+            pos
+        );
+        let (word, mask) = self.locate(pos);
+        if value {
+            self.words[word] |= mask;
+        } else {
+            self.words[word] &= !mask;
+        }
+    }
+
+    pub fn toggle(&mut self, pos: Position) {
+        assert!(
+            self.validate_position(pos),
+            "Position {:?} outside of grid!",
+            pos
+        );
+        let (word, mask) = self.locate(pos);
+        self.words[word] ^= mask;
+    }
+
+    pub fn iter_positions(&self) -> impl Iterator<Item = Position> + '_ {
+        (0..self.height).flat_map(move |y| (0..self.width).map(move |x| (y, x)))
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (Position, bool)> + '_ {
+        self.iter_positions().map(|pos| (pos, self[pos]))
+    }
+
+    /// Positions of the set cells, row by row, skipping empty words.
+    pub fn ones(&self) -> impl Iterator<Item = Position> + '_ {
+        self.words.iter().enumerate().flat_map(move |(i, &word)| {
+            let (y, x0) = (i / self.stride, (i % self.stride) * 64);
+            std::iter::successors((word != 0).then_some(word), |&w| {
+                Some(w & (w - 1)).filter(|&w| w != 0)
+            })
+            .map(move |w| (y, x0 + w.trailing_zeros() as usize))
+        })
+    }
+
+    pub fn count_ones(&self) -> usize {
+        self.words.iter().map(|w| w.count_ones() as usize).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.words.iter().all(|&w| w == 0)
+    }
+
+    fn neighbors<D: Direction + 'static>(&self, pos: Position) -> Vec<Position> {
+        D::all()
+            .iter()
+            .map(|d| d.add_unsigned(&pos, 1))
+            .filter(|&pos| self.validate_position_virtual(pos))
+            .map(|(y, x)| (y as usize, x as usize))
+            .collect()
+    }
+
+    pub fn neighbors_cardinal(&self, pos: Position) -> Vec<Position> {
+        self.neighbors::<CardinalDirection>(pos)
+    }
+
+    pub fn neighbors_ordinal(&self, pos: Position) -> Vec<Position> {
+        self.neighbors::<OrdinalDirection>(pos)
+    }
+
+    /// How many neighbors in the directions of `D` are set.
+    pub fn count_neighbors<D: Direction + 'static>(&self, pos: Position) -> usize {
+        D::all()
+            .iter()
+            .filter(|d| self.get_virtual(d.add_unsigned(&pos, 1)) == Some(true))
+            .count()
+    }
+
+    /// Clears the bits past `width` in every row's last word.
+    fn clear_padding(&mut self) {
+        if !self.width.is_multiple_of(64) {
+            let mask = (1 << (self.width % 64)) - 1;
+            for row in self.words.chunks_mut(self.stride) {
+                *row.last_mut().unwrap() &= mask;
+            }
+        }
+    }
+
+    /// Moves every cell by `(dy, dx)`. Cells moved off the grid are dropped and
+    /// the ones moved in are unset, so `shifted(1, 0)[(y, x)] == self[(y - 1, x)]`.
+    /// # Examples
+    /// ```
+    /// use utils::grid::BitGrid;
+    ///
+    /// let grid = BitGrid::from_str("#..\n.#.", |c| c == '#');
+    /// assert_eq!(grid.shifted(1, 1).to_string(), "...\n.#.\n");
+    /// assert_eq!(grid.shifted(0, -1).to_string(), "...\n#..\n");
+    /// ```
+    pub fn shifted(&self, dy: isize, dx: isize) -> Self {
+        let mut out = Self::new(self.height, self.width);
+        let (word_shift, bit_shift) = (dx.unsigned_abs() / 64, dx.unsigned_abs() % 64);
+        for y in 0..self.height {
+            let Some(src_y) = y.checked_add_signed(-dy).filter(|&y| y < self.height) else {
+                continue;
+            };
+            let src = &self.words[src_y * self.stride..(src_y + 1) * self.stride];
+            let word = |i: Option<usize>| i.and_then(|i| src.get(i)).copied().unwrap_or(0);
+            let dst = &mut out.words[y * self.stride..(y + 1) * self.stride];
+            for (w, dst) in dst.iter_mut().enumerate() {
+                *dst = if dx >= 0 {
+                    let lo = word(w.checked_sub(word_shift));
+                    let carry = word(w.checked_sub(word_shift + 1));
+                    if bit_shift == 0 {
+                        lo
+                    } else {
+                        lo << bit_shift | carry >> (64 - bit_shift)
+                    }
+                } else {
+                    let hi = word(Some(w + word_shift));
+                    let carry = word(Some(w + word_shift + 1));
+                    if bit_shift == 0 {
+                        hi
+                    } else {
+                        hi >> bit_shift | carry << (64 - bit_shift)
+                    }
+                };
+            }
+        }
+        out.clear_padding();
+        out
+    }
+
+    /// One copy of the grid per direction in `D`, shifted so that a cell is set
+    /// in it when that neighbor is set here.
+    pub fn neighbor_masks<D: Direction + 'static>(&self) -> Vec<Self> {
+        D::all()
+            .iter()
+            .map(|d| {
+                let (dy, dx) = d.dydx(1);
+                self.shifted(-dy, -dx)
+            })
+            .collect()
+    }
+
+    /// Cells that are set in at least `k` of `masks`, counted with saturating
+    /// bit-sliced counters, a word at a time. `masks` must have the same size.
+    pub fn at_least(masks: &[Self], k: usize) -> Self {
+        let (height, width) = masks.first().map_or((0, 0), Self::size);
+        if k == 0 {
+            return !&Self::new(height, width);
+        }
+        // levels[i] holds the cells seen in more than i masks so far.
+        let mut levels = vec![Self::new(height, width); k];
+        for mask in masks {
+            assert_eq!(mask.size(), (height, width), "Mask sizes differ!");
+            for (w, &m) in mask.words.iter().enumerate() {
+                for i in (1..k).rev() {
+                    let below = levels[i - 1].words[w];
+                    levels[i].words[w] |= below & m;
+                }
+                levels[0].words[w] |= m;
+            }
+        }
+        levels.pop().unwrap()
+    }
+
+    fn zip_with(&self, other: &Self, f: impl Fn(u64, u64) -> u64) -> Self {
+        assert_eq!(self.size(), other.size(), "Grid sizes differ!");
+        Self {
+            words: self
+                .words
+                .iter()
+                .zip(&other.words)
+                .map(|(&a, &b)| f(a, b))
+                .collect(),
+            ..*self
+        }
+    }
+
+    /// Cells set here but not in `other`.
+    pub fn and_not(&self, other: &Self) -> Self {
+        self.zip_with(other, |a, b| a & !b)
+    }
+}
+
+impl BitAnd for &BitGrid {
+    type Output = BitGrid;
+
+    fn bitand(self, other: Self) -> BitGrid {
+        self.zip_with(other, |a, b| a & b)
+    }
+}
+
+impl BitOr for &BitGrid {
+    type Output = BitGrid;
+
+    fn bitor(self, other: Self) -> BitGrid {
+        self.zip_with(other, |a, b| a | b)
+    }
+}
+
+impl BitXor for &BitGrid {
+    type Output = BitGrid;
+
+    fn bitxor(self, other: Self) -> BitGrid {
+        self.zip_with(other, |a, b| a ^ b)
+    }
+}
+
+impl Not for &BitGrid {
+    type Output = BitGrid;
+
+    fn not(self) -> BitGrid {
+        let mut out = BitGrid {
+            words: self.words.iter().map(|w| !w).collect(),
+            ..*self
+        };
+        out.clear_padding();
+        out
+    }
+}
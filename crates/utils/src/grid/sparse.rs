@@ -0,0 +1,252 @@
+use std::collections::{BTreeMap, HashMap};
+use std::fmt::{Debug, Display};
+use std::ops::Index;
+
+use super::Grid;
+use crate::direction::{CardinalDirection, Direction, OrdinalDirection, PositionVirtual};
+
+/// Unbounded grid that only stores the cells that are set, for simulations
+/// that grow outward. Coordinates may be negative, and the bounding box of the
+/// set cells is tracked as they are inserted and removed.
+/// # Examples
+/// ```
+/// use utils::direction::CardinalDirection;
+/// use utils::grid::SparseGrid;
+///
+/// let mut sand = SparseGrid::new();
+/// sand.insert((0, 0), 'o');
+/// sand.insert((-1, 2), '#');
+/// assert_eq!(sand.bounds(), Some(((-1, 0), (0, 2))));
+/// assert_eq!(sand.move_direction((0, 0), &CardinalDirection::West, 3), (0, -3));
+/// assert_eq!(sand.occupied_neighbors_ordinal((0, 1)).count(), 2);
+///
+/// let (grid, offset) = sand.to_grid('.');
+/// assert_eq!((grid.size(), offset), ((2, 3), (-1, 0)));
+/// assert_eq!(sand.to_string(), "..#\no..\n");
+///
+/// sand.remove((-1, 2));
+/// assert_eq!(sand.bounds(), Some(((0, 0), (0, 0))));
+/// sand.remove((0, 0));
+/// assert_eq!((sand.bounds(), sand.size()), (None, (0, 0)));
+/// ```
+#[derive(Debug, Clone)]
+pub struct SparseGrid<T> {
+    cells: HashMap<PositionVirtual, T>,
+    /// Number of set cells in every non-empty row, so the bounds are the first
+    /// and last keys and removing a cell never needs a rescan.
+    rows: BTreeMap<isize, usize>,
+    /// Like `rows`, for columns.
+    cols: BTreeMap<isize, usize>,
+}
+
+fn add_count(counts: &mut BTreeMap<isize, usize>, key: isize) {
+    *counts.entry(key).or_default() += 1;
+}
+
+fn remove_count(counts: &mut BTreeMap<isize, usize>, key: isize) {
+    if let Some(count) = counts.get_mut(&key) {
+        *count -= 1;
+        if *count == 0 {
+            counts.remove(&key);
+        }
+    }
+}
+
+/// First and last key of non-empty counts.
+fn span(counts: &BTreeMap<isize, usize>) -> Option<(isize, isize)> {
+    Some((*counts.first_key_value()?.0, *counts.last_key_value()?.0))
+}
+
+impl<T> Default for SparseGrid<T> {
+    fn default() -> Self {
+        Self {
+            cells: HashMap::new(),
+            rows: BTreeMap::new(),
+            cols: BTreeMap::new(),
+        }
+    }
+}
+
+impl<T> SparseGrid<T> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets a cell, returning its previous value.
+    pub fn insert(&mut self, pos: PositionVirtual, value: T) -> Option<T> {
+        let previous = self.cells.insert(pos, value);
+        if previous.is_none() {
+            add_count(&mut self.rows, pos.0);
+            add_count(&mut self.cols, pos.1);
+        }
+        previous
+    }
+
+    /// Clears a cell, shrinking the bounding box if it was the last one on its edge.
+    pub fn remove(&mut self, (y, x): PositionVirtual) -> Option<T> {
+        let value = self.cells.remove(&(y, x))?;
+        remove_count(&mut self.rows, y);
+        remove_count(&mut self.cols, x);
+        Some(value)
+    }
+
+    pub fn get(&self, pos: PositionVirtual) -> Option<&T> {
+        self.cells.get(&pos)
+    }
+
+    pub fn get_mut(&mut self, pos: PositionVirtual) -> Option<&mut T> {
+        self.cells.get_mut(&pos)
+    }
+
+    pub fn contains(&self, pos: PositionVirtual) -> bool {
+        self.cells.contains_key(&pos)
+    }
+
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    /// Inclusive top left and bottom right corners of the set cells, `None` if there are none.
+    pub fn bounds(&self) -> Option<(PositionVirtual, PositionVirtual)> {
+        let (min_y, max_y) = span(&self.rows)?;
+        let (min_x, max_x) = span(&self.cols)?;
+        Some(((min_y, min_x), (max_y, max_x)))
+    }
+
+    /// `(height, width)` of the bounding box.
+    pub fn size(&self) -> (usize, usize) {
+        self.bounds()
+            .map_or((0, 0), |((min_y, min_x), (max_y, max_x))| {
+                ((max_y - min_y) as usize + 1, (max_x - min_x) as usize + 1)
+            })
+    }
+
+    /// Set cells, in no particular order.
+    pub fn iter(&self) -> impl Iterator<Item = (PositionVirtual, &T)> {
+        self.cells.iter().map(|(&pos, t)| (pos, t))
+    }
+
+    pub fn positions(&self) -> impl Iterator<Item = PositionVirtual> + '_ {
+        self.cells.keys().copied()
+    }
+
+    pub fn move_direction(
+        &self,
+        pos: PositionVirtual,
+        direction: &impl Direction,
+        distance: usize,
+    ) -> PositionVirtual {
+        direction.add(&pos, distance)
+    }
+
+    /// All neighbors, whether they are set or not. The grid has no edges.
+    fn neighbors<D: Direction + 'static>(&self, pos: PositionVirtual) -> Vec<PositionVirtual> {
+        D::all().iter().map(|d| d.add(&pos, 1)).collect()
+    }
+
+    pub fn neighbors_cardinal(&self, pos: PositionVirtual) -> Vec<PositionVirtual> {
+        self.neighbors::<CardinalDirection>(pos)
+    }
+
+    pub fn neighbors_ordinal(&self, pos: PositionVirtual) -> Vec<PositionVirtual> {
+        self.neighbors::<OrdinalDirection>(pos)
+    }
+
+    /// Neighbors that are set, with their values.
+    pub fn occupied_neighbors<D: Direction + 'static>(
+        &self,
+        pos: PositionVirtual,
+    ) -> impl Iterator<Item = (PositionVirtual, &T)> + '_ {
+        D::all().iter().filter_map(move |d| {
+            let pos = d.add(&pos, 1);
+            self.cells.get(&pos).map(|t| (pos, t))
+        })
+    }
+
+    pub fn occupied_neighbors_cardinal(
+        &self,
+        pos: PositionVirtual,
+    ) -> impl Iterator<Item = (PositionVirtual, &T)> + '_ {
+        self.occupied_neighbors::<CardinalDirection>(pos)
+    }
+
+    pub fn occupied_neighbors_ordinal(
+        &self,
+        pos: PositionVirtual,
+    ) -> impl Iterator<Item = (PositionVirtual, &T)> + '_ {
+        self.occupied_neighbors::<OrdinalDirection>(pos)
+    }
+}
+
+impl<T: Debug + Clone> SparseGrid<T> {
+    /// Renders the bounding box into a dense grid, filling unset cells with
+    /// `empty`. Also returns the offset of the grid's `(0, 0)`, so that
+    /// `(y, x)` here is `(y - offset.0, x - offset.1)` in the dense grid.
+    /// Panics if the grid is empty.
+    pub fn to_grid(&self, empty: T) -> (Grid<T>, PositionVirtual) {
+        let (min, _) = self.bounds().expect("Empty grid!");
+        let (height, width) = self.size();
+        let mut grid = Grid::blank(height, width, empty);
+        for (&(y, x), t) in &self.cells {
+            grid[((y - min.0) as usize, (x - min.1) as usize)] = t.clone();
+        }
+        (grid, min)
+    }
+}
+
+impl<T: Debug + Clone> Grid<T> {
+    /// Keeps the cells for which `keep` is true, at the same positions.
+    pub fn to_sparse(&self, keep: impl Fn(&T) -> bool) -> SparseGrid<T> {
+        self.iter()
+            .filter(|(_, t)| keep(t))
+            .map(|((y, x), t)| ((y as isize, x as isize), t.clone()))
+            .collect()
+    }
+}
+
+impl<T> Index<PositionVirtual> for SparseGrid<T> {
+    type Output = T;
+
+    fn index(&self, pos: PositionVirtual) -> &T {
+        &self.cells[&pos]
+    }
+}
+
+impl<T> FromIterator<(PositionVirtual, T)> for SparseGrid<T> {
+    fn from_iter<I: IntoIterator<Item = (PositionVirtual, T)>>(iter: I) -> Self {
+        let mut grid = Self::new();
+        grid.extend(iter);
+        grid
+    }
+}
+
+impl<T> Extend<(PositionVirtual, T)> for SparseGrid<T> {
+    fn extend<I: IntoIterator<Item = (PositionVirtual, T)>>(&mut self, iter: I) {
+        for (pos, value) in iter {
+            self.insert(pos, value);
+        }
+    }
+}
+
+/// Draws the bounding box, with `.` for unset cells.
+impl<T: Display> Display for SparseGrid<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let Some(((min_y, min_x), (max_y, max_x))) = self.bounds() else {
+            return Ok(());
+        };
+        for y in min_y..=max_y {
+            for x in min_x..=max_x {
+                match self.cells.get(&(y, x)) {
+                    Some(t) => write!(f, "{}", t)?,
+                    None => write!(f, ".")?,
+                }
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,231 @@
+use std::fmt::{Debug, Display};
+use std::ops::Index;
+
+use super::{Grid, Position};
+
+/// One of the eight ways to rotate or mirror a rectangle onto itself.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Symmetry {
+    Identity,
+    RotateCw,
+    Rotate180,
+    RotateCcw,
+    /// Mirrors left to right.
+    FlipHorizontal,
+    /// Mirrors top to bottom.
+    FlipVertical,
+    /// Mirrors along the main diagonal, swapping rows and columns.
+    Transpose,
+    /// Mirrors along the other diagonal.
+    AntiTranspose,
+}
+
+impl Symmetry {
+    pub const ALL: [Symmetry; 8] = [
+        Symmetry::Identity,
+        Symmetry::RotateCw,
+        Symmetry::Rotate180,
+        Symmetry::RotateCcw,
+        Symmetry::FlipHorizontal,
+        Symmetry::FlipVertical,
+        Symmetry::Transpose,
+        Symmetry::AntiTranspose,
+    ];
+
+    /// The symmetry that undoes this one.
+    pub fn inverse(self) -> Self {
+        match self {
+            Symmetry::RotateCw => Symmetry::RotateCcw,
+            Symmetry::RotateCcw => Symmetry::RotateCw,
+            other => other,
+        }
+    }
+
+    pub fn swaps_axes(self) -> bool {
+        matches!(
+            self,
+            Symmetry::RotateCw
+                | Symmetry::RotateCcw
+                | Symmetry::Transpose
+                | Symmetry::AntiTranspose
+        )
+    }
+
+    /// `(height, width)` of a grid of `size` after the transformation.
+    pub fn size(self, (height, width): (usize, usize)) -> (usize, usize) {
+        if self.swaps_axes() {
+            (width, height)
+        } else {
+            (height, width)
+        }
+    }
+
+    /// Where the cell at `(y, x)` of a grid of `size` ends up after the transformation.
+    /// # Examples
+    /// ```
+    /// use utils::grid::Symmetry;
+    ///
+    /// let size = (2, 3);
+    /// assert_eq!(Symmetry::RotateCw.map_position((0, 0), size), (0, 1));
+    /// assert_eq!(Symmetry::RotateCw.map_position((1, 2), size), (2, 0));
+    /// for symmetry in Symmetry::ALL {
+    ///     let moved = symmetry.map_position((1, 0), size);
+    ///     assert_eq!(symmetry.unmap_position(moved, size), (1, 0));
+    /// }
+    /// ```
+    pub fn map_position(self, (y, x): Position, (height, width): (usize, usize)) -> Position {
+        let (last_y, last_x) = (height - 1, width - 1);
+        match self {
+            Symmetry::Identity => (y, x),
+            Symmetry::RotateCw => (x, last_y - y),
+            Symmetry::Rotate180 => (last_y - y, last_x - x),
+            Symmetry::RotateCcw => (last_x - x, y),
+            Symmetry::FlipHorizontal => (y, last_x - x),
+            Symmetry::FlipVertical => (last_y - y, x),
+            Symmetry::Transpose => (x, y),
+            Symmetry::AntiTranspose => (last_x - x, last_y - y),
+        }
+    }
+
+    /// Inverse of [`Symmetry::map_position`]: where a cell of the transformed
+    /// grid came from, `size` being that of the original grid.
+    pub fn unmap_position(self, pos: Position, size: (usize, usize)) -> Position {
+        self.inverse().map_position(pos, self.size(size))
+    }
+}
+
+/// A grid seen through a [`Symmetry`], without copying it.
+#[derive(Debug, Clone, Copy)]
+pub struct TransformedView<'a, T: Debug + Clone> {
+    grid: &'a Grid<T>,
+    symmetry: Symmetry,
+}
+
+impl<'a, T: Debug + Clone> TransformedView<'a, T> {
+    pub fn symmetry(&self) -> Symmetry {
+        self.symmetry
+    }
+
+    pub fn size(&self) -> (usize, usize) {
+        self.symmetry.size(self.grid.size())
+    }
+
+    pub fn height(&self) -> usize {
+        self.size().0
+    }
+
+    pub fn width(&self) -> usize {
+        self.size().1
+    }
+
+    /// Position in the underlying grid of the view's cell at `pos`.
+    pub fn to_original(&self, pos: Position) -> Position {
+        self.symmetry.unmap_position(pos, self.grid.size())
+    }
+
+    pub fn get(&self, (y, x): Position) -> Option<&'a T> {
+        let (height, width) = self.size();
+        (y < height && x < width).then(|| &self.grid[self.to_original((y, x))])
+    }
+
+    /// Cells with their positions in the view, row by row.
+    pub fn iter(&self) -> impl Iterator<Item = (Position, &'a T)> + '_ {
+        let (height, width) = self.size();
+        (0..height)
+            .flat_map(move |y| (0..width).map(move |x| (y, x)))
+            .map(|pos| (pos, &self.grid[self.to_original(pos)]))
+    }
+
+    pub fn to_grid(&self) -> Grid<T> {
+        let (height, width) = self.size();
+        Grid {
+            data: self.iter().map(|(_, t)| t.clone()).collect(),
+            height,
+            width,
+        }
+    }
+}
+
+impl<T: Debug + Clone> Index<Position> for TransformedView<'_, T> {
+    type Output = T;
+
+    fn index(&self, pos: Position) -> &T {
+        self.get(pos).expect("Position outside of view!")
+    }
+}
+
+impl<T: Debug + Clone + Display> Display for TransformedView<'_, T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for ((_, x), cell) in self.iter() {
+            write!(f, "{}", cell)?;
+            if x + 1 == self.width() {
+                writeln!(f)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<T: Debug + Clone> Grid<T> {
+    /// Views the grid rotated or mirrored, without copying it.
+    /// # Examples
+    /// ```
+    /// use utils::grid::{Grid, Symmetry};
+    ///
+    /// let grid = Grid::char_grid("ab\ncd\nef");
+    /// let view = grid.view(Symmetry::RotateCw);
+    /// assert_eq!(view.to_string(), "eca\nfdb\n");
+    /// assert_eq!((view[(0, 0)], view.to_original((0, 0))), ('e', (2, 0)));
+    /// assert_eq!(grid.rotate_cw().to_string(), view.to_string());
+    /// assert_eq!(grid.transpose().to_string(), "ace\nbdf\n");
+    /// assert_eq!(grid.flip_horizontal().to_string(), "ba\ndc\nfe\n");
+    /// assert_eq!(grid.rotate_ccw().rotate_cw().to_string(), grid.to_string());
+    /// ```
+    pub fn view(&self, symmetry: Symmetry) -> TransformedView<'_, T> {
+        TransformedView {
+            grid: self,
+            symmetry,
+        }
+    }
+
+    pub fn transform(&self, symmetry: Symmetry) -> Self {
+        self.view(symmetry).to_grid()
+    }
+
+    pub fn transpose(&self) -> Self {
+        self.transform(Symmetry::Transpose)
+    }
+
+    pub fn rotate_cw(&self) -> Self {
+        self.transform(Symmetry::RotateCw)
+    }
+
+    pub fn rotate_ccw(&self) -> Self {
+        self.transform(Symmetry::RotateCcw)
+    }
+
+    pub fn flip_horizontal(&self) -> Self {
+        self.transform(Symmetry::FlipHorizontal)
+    }
+
+    pub fn flip_vertical(&self) -> Self {
+        self.transform(Symmetry::FlipVertical)
+    }
+
+    /// Views of all eight rotations and reflections, starting with the grid itself.
+    /// Symmetric grids give some variants more than once.
+    /// # Examples
+    /// ```
+    /// use std::collections::HashSet;
+    /// use utils::grid::Grid;
+    ///
+    /// let shape = Grid::char_grid("##\n#.");
+    /// let distinct = shape.variants().map(|v| v.to_string()).collect::<HashSet<_>>();
+    /// assert_eq!(distinct.len(), 4);
+    /// ```
+    pub fn variants(&self) -> impl Iterator<Item = TransformedView<'_, T>> + '_ {
+        Symmetry::ALL
+            .into_iter()
+            .map(|symmetry| self.view(symmetry))
+    }
+}
@@ -0,0 +1,187 @@
+use std::fmt::Debug;
+use std::ops::Index;
+
+use super::{Grid, Obstructs, Position};
+use crate::direction::{CardinalDirection, Direction, OrdinalDirection, PositionVirtual};
+
+impl<T: Debug + Clone> Grid<T> {
+    /// Wraps any position onto the grid, as if it were a torus.
+    /// # Examples
+    /// ```
+    /// use utils::grid::Grid;
+    ///
+    /// let grid = Grid::blank(3, 4, 0);
+    /// assert_eq!(grid.wrap_position((-1, 9)), (2, 1));
+    /// ```
+    pub fn wrap_position(&self, (y, x): PositionVirtual) -> Position {
+        (
+            y.rem_euclid(self.height as isize) as usize,
+            x.rem_euclid(self.width as isize) as usize,
+        )
+    }
+
+    /// Views the grid with opposite edges joined, so nothing is ever out of bounds.
+    pub fn wrapping(&self) -> WrappingGrid<'_, T> {
+        WrappingGrid { grid: self }
+    }
+}
+
+/// A grid whose edges wrap around, see [`Grid::wrapping`]. Lookups, neighbors,
+/// movement and path finding all work modulo the height and width.
+/// # Examples
+/// ```
+/// use utils::direction::CardinalDirection;
+/// use utils::grid::Grid;
+///
+/// let grid = Grid::char_grid("ab\ncd\nef");
+/// let wrapping = grid.wrapping();
+/// assert_eq!((wrapping[(-1, -1)], wrapping[(4, 3)]), ('f', 'd'));
+/// assert_eq!(wrapping.neighbors_cardinal((0, 0)), [(2, 0), (1, 0), (0, 1), (0, 1)]);
+/// assert_eq!(wrapping.move_direction((0, 1), &CardinalDirection::East, 3), (0, 0));
+/// assert_eq!(wrapping.move_by((2, 1), (5, -4), 10), (1, 1));
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct WrappingGrid<'a, T: Debug + Clone> {
+    grid: &'a Grid<T>,
+}
+
+impl<'a, T: Debug + Clone> WrappingGrid<'a, T> {
+    pub fn grid(&self) -> &'a Grid<T> {
+        self.grid
+    }
+
+    pub fn wrap(&self, pos: PositionVirtual) -> Position {
+        self.grid.wrap_position(pos)
+    }
+
+    pub fn get_virtual(&self, pos: PositionVirtual) -> &'a T {
+        &self.grid[self.wrap(pos)]
+    }
+
+    /// Moves `distance` steps in `direction`.
+    pub fn move_direction(
+        &self,
+        pos: Position,
+        direction: &impl Direction,
+        distance: usize,
+    ) -> Position {
+        self.wrap(direction.add_unsigned(&pos, distance))
+    }
+
+    /// Moves `steps` times by `velocity`, like a robot teleporting across the edges.
+    pub fn move_by(&self, (y, x): Position, (dy, dx): PositionVirtual, steps: isize) -> Position {
+        self.wrap((y as isize + dy * steps, x as isize + dx * steps))
+    }
+
+    /// Neighbors in the order of `D::all()`. On grids narrower than three
+    /// cells, the same neighbor can appear more than once.
+    pub fn neighbor_iter<D: Direction + 'static>(
+        &self,
+        pos: Position,
+    ) -> impl Iterator<Item = (Position, &'a T)> + '_ {
+        D::all().iter().map(move |direction| {
+            let pos = self.move_direction(pos, direction, 1);
+            (pos, &self.grid[pos])
+        })
+    }
+
+    pub fn neighbors_cardinal(&self, pos: Position) -> Vec<Position> {
+        self.neighbor_iter::<CardinalDirection>(pos)
+            .map(|(pos, _)| pos)
+            .collect()
+    }
+
+    pub fn neighbors_ordinal(&self, pos: Position) -> Vec<Position> {
+        self.neighbor_iter::<OrdinalDirection>(pos)
+            .map(|(pos, _)| pos)
+            .collect()
+    }
+
+    /// Manhattan distance, going across the edges when that is shorter.
+    pub fn distance_cardinal(&self, (y1, x1): Position, (y2, x2): Position) -> usize {
+        let dy = y1.abs_diff(y2);
+        let dx = x1.abs_diff(x2);
+        dy.min(self.grid.height - dy) + dx.min(self.grid.width - dx)
+    }
+
+    /// Chebyshev distance, going across the edges when that is shorter.
+    pub fn distance_ordinal(&self, (y1, x1): Position, (y2, x2): Position) -> usize {
+        let dy = y1.abs_diff(y2);
+        let dx = x1.abs_diff(x2);
+        dy.min(self.grid.height - dy)
+            .max(dx.min(self.grid.width - dx))
+    }
+
+    /// Lower bound on the steps between two positions when moving in the directions `D`.
+    pub fn distance<D: Direction + 'static>(&self, pos_a: Position, pos_b: Position) -> usize {
+        if D::has_diagonals() {
+            self.distance_ordinal(pos_a, pos_b)
+        } else {
+            self.distance_cardinal(pos_a, pos_b)
+        }
+    }
+}
+
+impl<T: Debug + Clone + Obstructs> WrappingGrid<'_, T> {
+    /// Like [`Grid::astar`], with paths allowed to cross the edges.
+    /// # Examples
+    /// ```
+    /// use utils::grid::{Grid, Obstructs};
+    ///
+    /// #[derive(Debug, Clone)]
+    /// struct Wall(bool);
+    ///
+    /// impl Obstructs for Wall {
+    ///     fn obstructs(&self) -> bool {
+    ///         self.0
+    ///     }
+    /// }
+    ///
+    /// let grid = Grid::from_str(".....\n.###.\n.#.#.\n.###.\n.....", |c| Wall(c == '#'));
+    /// let wrapping = grid.wrapping();
+    /// assert_eq!(wrapping.astar_cardinal(&(0, 0), &(4, 4)).unwrap().1, 2);
+    /// assert_eq!(wrapping.astar_ordinal(&(0, 0), &(4, 4)).unwrap().1, 1);
+    /// assert_eq!(wrapping.astar_ordinal(&(0, 0), &(2, 2)), None);
+    /// ```
+    pub fn astar<D: Direction + 'static>(
+        &self,
+        start: &Position,
+        end: &Position,
+    ) -> Option<(Vec<Position>, usize)> {
+        pathfinding::prelude::astar(
+            start,
+            |&pos| {
+                self.neighbor_iter::<D>(pos)
+                    .filter(|(_, t)| !t.obstructs())
+                    .map(|(n, _)| (n, 1))
+                    .collect::<Vec<_>>()
+            },
+            |pos| self.distance::<D>(*pos, *end),
+            |pos| pos == end,
+        )
+    }
+
+    pub fn astar_cardinal(
+        &self,
+        start: &Position,
+        end: &Position,
+    ) -> Option<(Vec<Position>, usize)> {
+        self.astar::<CardinalDirection>(start, end)
+    }
+
+    pub fn astar_ordinal(
+        &self,
+        start: &Position,
+        end: &Position,
+    ) -> Option<(Vec<Position>, usize)> {
+        self.astar::<OrdinalDirection>(start, end)
+    }
+}
+
+impl<T: Debug + Clone> Index<PositionVirtual> for WrappingGrid<'_, T> {
+    type Output = T;
+
+    fn index(&self, pos: PositionVirtual) -> &T {
+        self.get_virtual(pos)
+    }
+}
@@ -0,0 +1,202 @@
+use std::borrow::Cow;
+use std::fmt::Display;
+
+use crate::crypt::{self, InputKey, KEY_ENV};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Trim {
+    /// Strip leading and trailing whitespace from the whole input.
+    #[default]
+    Whitespace,
+    /// Keep the input as is, for layouts where leading or trailing spaces matter.
+    None,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct InputOptions {
+    pub trim: Trim,
+    /// The input is a grid, so lines of different lengths are flagged.
+    pub grid: bool,
+}
+
+impl InputOptions {
+    pub const fn untrimmed() -> Self {
+        Self {
+            trim: Trim::None,
+            grid: false,
+        }
+    }
+
+    pub const fn grid() -> Self {
+        Self {
+            trim: Trim::Whitespace,
+            grid: true,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InputIssue {
+    Empty,
+    /// The input is encrypted and no key is set.
+    Encrypted,
+    NonAscii {
+        line: usize,
+        column: usize,
+        ch: char,
+    },
+    RaggedLine {
+        line: usize,
+        expected: usize,
+        found: usize,
+    },
+    TrailingWhitespace {
+        line: usize,
+    },
+    MissingFinalNewline,
+}
+
+impl InputIssue {
+    /// Whether the input cannot be solved at all. Such example checks are
+    /// skipped, while checks on the real input fail.
+    pub fn is_fatal(&self) -> bool {
+        matches!(self, Self::Empty)
+    }
+
+    /// Whether the input is not available here, e.g. without [`KEY_ENV`] in a
+    /// fork's CI. Such checks are skipped for any input kind.
+    pub fn is_unavailable(&self) -> bool {
+        matches!(self, Self::Encrypted)
+    }
+}
+
+impl Display for InputIssue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Empty => write!(f, "input is empty"),
+            Self::Encrypted => write!(f, "input is encrypted and {} is not set", KEY_ENV),
+            Self::NonAscii { line, column, ch } => {
+                write!(
+                    f,
+                    "line {}, column {}: non-ASCII character {:?}",
+                    line, column, ch
+                )
+            }
+            Self::RaggedLine {
+                line,
+                expected,
+                found,
+            } => write!(
+                f,
+                "line {}: length {} differs from the first line's length {}",
+                line, found, expected
+            ),
+            Self::TrailingWhitespace { line } => write!(f, "line {}: trailing whitespace", line),
+            Self::MissingFinalNewline => write!(f, "missing final newline"),
+        }
+    }
+}
+
+/// Puzzle input after line ending normalization and trimming, with anything suspicious flagged.
+#[derive(Debug, Clone, Default)]
+pub struct Input<'a> {
+    pub text: Cow<'a, str>,
+    pub issues: Vec<InputIssue>,
+}
+
+impl<'a> Input<'a> {
+    pub fn fatal_issue(&self) -> Option<&InputIssue> {
+        self.issues.iter().find(|issue| issue.is_fatal())
+    }
+
+    pub fn unavailable_issue(&self) -> Option<&InputIssue> {
+        self.issues.iter().find(|issue| issue.is_unavailable())
+    }
+}
+
+/// Decrypts the input if needed (see [`crypt`]), normalizes `\r\n` to `\n`, validates the raw text and trims it according to `options`.
+/// # Examples
+/// ```
+/// use utils::input::{prepare, InputIssue, InputOptions};
+///
+/// let input = prepare("abc\r\nd \r\n", &InputOptions::default());
+/// assert_eq!(input.text, "abc\nd");
+/// assert_eq!(input.issues, vec![InputIssue::TrailingWhitespace { line: 2 }]);
+///
+/// let input = prepare("abc\nd\n", &InputOptions::grid());
+/// assert_eq!(
+///     input.issues,
+///     vec![InputIssue::RaggedLine { line: 2, expected: 3, found: 1 }]
+/// );
+/// assert_eq!(prepare("", &InputOptions::default()).issues, vec![InputIssue::Empty]);
+/// ```
+pub fn prepare<'a>(raw: &'a str, options: &InputOptions) -> Input<'a> {
+    let raw = if crypt::is_encrypted(raw) {
+        let Some(key) = InputKey::from_env() else {
+            return Input {
+                text: Cow::Borrowed(""),
+                issues: vec![InputIssue::Encrypted],
+            };
+        };
+        let plaintext = key.and_then(|key| crypt::decrypt(raw, &key));
+        Cow::Owned(plaintext.unwrap_or_else(|e| panic!("{}", e)))
+    } else {
+        Cow::Borrowed(raw)
+    };
+    let normalized = if raw.contains('\r') {
+        Cow::Owned(raw.replace("\r\n", "\n"))
+    } else {
+        raw
+    };
+    let issues = validate(&normalized, options);
+    let text = match (options.trim, normalized) {
+        (Trim::None, text) => text,
+        (Trim::Whitespace, Cow::Borrowed(text)) => Cow::Borrowed(text.trim()),
+        (Trim::Whitespace, Cow::Owned(text)) => Cow::Owned(text.trim().to_string()),
+    };
+    Input { text, issues }
+}
+
+/// Flags problems in already normalized input. Ragged lines are only checked
+/// for grid inputs (see [`InputOptions::grid`]).
+/// Only the first ragged line and the first line with trailing whitespace are reported.
+pub fn validate(text: &str, options: &InputOptions) -> Vec<InputIssue> {
+    if text.trim().is_empty() {
+        return vec![InputIssue::Empty];
+    }
+    let mut issues = vec![];
+    let mut width = None;
+    let mut ragged = false;
+    let mut trailing = false;
+    for (y, line) in text.lines().enumerate() {
+        if let Some((x, ch)) = line.chars().enumerate().find(|(_, c)| !c.is_ascii()) {
+            issues.push(InputIssue::NonAscii {
+                line: y + 1,
+                column: x + 1,
+                ch,
+            });
+        }
+        let len = line.chars().count();
+        match width {
+            _ if !options.grid => {}
+            None => width = Some(len),
+            Some(expected) if expected != len && !ragged && !line.is_empty() => {
+                ragged = true;
+                issues.push(InputIssue::RaggedLine {
+                    line: y + 1,
+                    expected,
+                    found: len,
+                });
+            }
+            _ => {}
+        }
+        if line.ends_with(char::is_whitespace) && !trailing {
+            trailing = true;
+            issues.push(InputIssue::TrailingWhitespace { line: y + 1 });
+        }
+    }
+    if !text.ends_with('\n') {
+        issues.push(InputIssue::MissingFinalNewline);
+    }
+    issues
+}
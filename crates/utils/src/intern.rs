@@ -0,0 +1,221 @@
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::ops::{Index, IndexMut};
+
+/// Dense id handed out by an [`Interner`], starting at 0.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Id(u32);
+
+impl Id {
+    pub fn new(index: usize) -> Self {
+        Self(u32::try_from(index).expect("Too many interned names"))
+    }
+
+    pub fn index(self) -> usize {
+        self.0 as usize
+    }
+}
+
+impl Display for Id {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "#{}", self.0)
+    }
+}
+
+/// Assigns ids to names in order of first appearance, borrowing the names.
+/// # Examples
+/// ```
+/// use utils::intern::Interner;
+///
+/// let mut names = Interner::new();
+/// let you = names.intern("you");
+/// let out = names.intern("out");
+/// assert_eq!(names.intern("you"), you);
+/// assert_eq!((you.index(), out.index()), (0, 1));
+/// assert_eq!(names.resolve(out), "out");
+///
+/// let symbols = names.freeze();
+/// assert_eq!(symbols.get("out"), Some(out));
+/// assert_eq!(symbols.get("svr"), None);
+///
+/// let mut visits = symbols.map(0);
+/// visits[out] += 2;
+/// assert_eq!(visits.iter().collect::<Vec<_>>(), [(you, &0), (out, &2)]);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct Interner<'a> {
+    names: Vec<&'a str>,
+    ids: HashMap<&'a str, Id>,
+}
+
+impl<'a> Interner<'a> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the name's id, assigning the next one if it is new.
+    pub fn intern(&mut self, name: &'a str) -> Id {
+        *self.ids.entry(name).or_insert_with(|| {
+            self.names.push(name);
+            Id::new(self.names.len() - 1)
+        })
+    }
+
+    pub fn get(&self, name: &str) -> Option<Id> {
+        self.ids.get(name).copied()
+    }
+
+    pub fn resolve(&self, id: Id) -> &'a str {
+        self.names[id.index()]
+    }
+
+    pub fn len(&self) -> usize {
+        self.names.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.names.is_empty()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (Id, &'a str)> + '_ {
+        self.names
+            .iter()
+            .enumerate()
+            .map(|(i, &name)| (Id::new(i), name))
+    }
+
+    /// Stops assigning ids, e.g. once the input is parsed.
+    pub fn freeze(self) -> Symbols<'a> {
+        Symbols {
+            names: self.names.into_boxed_slice(),
+            ids: self.ids,
+        }
+    }
+}
+
+/// Read-only table of interned names, see [`Interner::freeze`].
+#[derive(Debug, Clone, Default)]
+pub struct Symbols<'a> {
+    names: Box<[&'a str]>,
+    ids: HashMap<&'a str, Id>,
+}
+
+impl<'a> Symbols<'a> {
+    pub fn get(&self, name: &str) -> Option<Id> {
+        self.ids.get(name).copied()
+    }
+
+    pub fn resolve(&self, id: Id) -> &'a str {
+        self.names[id.index()]
+    }
+
+    pub fn len(&self) -> usize {
+        self.names.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.names.is_empty()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (Id, &'a str)> + '_ {
+        self.names
+            .iter()
+            .enumerate()
+            .map(|(i, &name)| (Id::new(i), name))
+    }
+
+    /// A map with an entry for every name, all set to `value`.
+    pub fn map<T: Clone>(&self, value: T) -> IdMap<T> {
+        IdMap::filled(self.len(), value)
+    }
+}
+
+/// `Vec`-backed map keyed by [`Id`], a faster replacement for `HashMap<&str, T>`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IdMap<T> {
+    values: Vec<T>,
+}
+
+impl<T> Default for IdMap<T> {
+    fn default() -> Self {
+        Self { values: vec![] }
+    }
+}
+
+impl<T> IdMap<T> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn filled(len: usize, value: T) -> Self
+    where
+        T: Clone,
+    {
+        Self {
+            values: vec![value; len],
+        }
+    }
+
+    pub fn get(&self, id: Id) -> Option<&T> {
+        self.values.get(id.index())
+    }
+
+    pub fn get_mut(&mut self, id: Id) -> Option<&mut T> {
+        self.values.get_mut(id.index())
+    }
+
+    /// Sets the value for `id`, filling any gap before it with defaults.
+    pub fn insert(&mut self, id: Id, value: T)
+    where
+        T: Default,
+    {
+        if id.index() >= self.values.len() {
+            self.values.resize_with(id.index() + 1, T::default);
+        }
+        self.values[id.index()] = value;
+    }
+
+    /// The value for `id`, inserting a default first if it is missing.
+    pub fn entry(&mut self, id: Id) -> &mut T
+    where
+        T: Default,
+    {
+        if id.index() >= self.values.len() {
+            self.values.resize_with(id.index() + 1, T::default);
+        }
+        &mut self.values[id.index()]
+    }
+
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (Id, &T)> {
+        self.values
+            .iter()
+            .enumerate()
+            .map(|(i, value)| (Id::new(i), value))
+    }
+
+    pub fn values(&self) -> std::slice::Iter<'_, T> {
+        self.values.iter()
+    }
+}
+
+impl<T> Index<Id> for IdMap<T> {
+    type Output = T;
+
+    fn index(&self, id: Id) -> &T {
+        &self.values[id.index()]
+    }
+}
+
+impl<T> IndexMut<Id> for IdMap<T> {
+    fn index_mut(&mut self, id: Id) -> &mut T {
+        &mut self.values[id.index()]
+    }
+}
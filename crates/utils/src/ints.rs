@@ -0,0 +1,233 @@
+use std::marker::PhantomData;
+
+use crate::parse::{ParseError, ParseResult};
+
+/// Primitive integers that [`ints`] and [`strict_ints`] can produce.
+pub trait Int: Copy {
+    const SIGNED: bool;
+
+    /// `None` if the value does not fit.
+    fn from_magnitude(negative: bool, magnitude: u128) -> Option<Self>;
+}
+
+macro_rules! int_impl {
+    ($signed:expr => $($t:ty),*) => {
+        $(
+            impl Int for $t {
+                const SIGNED: bool = $signed;
+
+                fn from_magnitude(negative: bool, magnitude: u128) -> Option<Self> {
+                    if negative {
+                        Self::try_from(0i128.checked_sub_unsigned(magnitude)?).ok()
+                    } else {
+                        Self::try_from(magnitude).ok()
+                    }
+                }
+            }
+        )*
+    };
+}
+
+int_impl!(false => u8, u16, u32, u64, u128, usize);
+int_impl!(true => i8, i16, i32, i64, i128, isize);
+
+/// Whether all eight bytes of `chunk` are ASCII digits.
+fn all_digits(chunk: u64) -> bool {
+    let below = chunk.wrapping_sub(0x3030_3030_3030_3030);
+    let above = chunk.wrapping_add(0x4646_4646_4646_4646);
+    (below | above) & 0x8080_8080_8080_8080 == 0
+}
+
+/// Parses eight ASCII digits at once (SWAR), `chunk` being their little-endian bytes.
+fn eight_digits(chunk: u64) -> u64 {
+    let v = chunk - 0x3030_3030_3030_3030;
+    let v = (v * 10 + (v >> 8)) & 0x00FF_00FF_00FF_00FF;
+    let v = (v * 100 + (v >> 16)) & 0x0000_FFFF_0000_FFFF;
+    (v * 10000 + (v >> 32)) & 0xFFFF_FFFF
+}
+
+/// Length of the run of digits at the start of `bytes`.
+fn digit_run(bytes: &[u8]) -> usize {
+    let mut len = 0;
+    while let Some(chunk) = bytes.get(len..len + 8) {
+        if !all_digits(u64::from_le_bytes(chunk.try_into().unwrap())) {
+            break;
+        }
+        len += 8;
+    }
+    len + bytes[len..]
+        .iter()
+        .position(|b| !b.is_ascii_digit())
+        .unwrap_or(bytes.len() - len)
+}
+
+/// Value of a run of ASCII digits, `None` on overflow.
+fn magnitude(digits: &[u8]) -> Option<u128> {
+    if digits.len() <= 19 {
+        let (chunks, remainder) = digits.as_chunks::<8>();
+        let mut value = 0u64;
+        for &chunk in chunks {
+            value = value * 100_000_000 + eight_digits(u64::from_le_bytes(chunk));
+        }
+        for &b in remainder {
+            value = value * 10 + (b - b'0') as u64;
+        }
+        Some(value as u128)
+    } else {
+        digits.iter().try_fold(0u128, |value, &b| {
+            value.checked_mul(10)?.checked_add((b - b'0') as u128)
+        })
+    }
+}
+
+/// Whether a `-` at `pos` is a sign: the type is signed, a digit follows and
+/// none precedes, so ranges like `1-3` still give `1` and `3`.
+fn is_sign<T: Int>(bytes: &[u8], pos: usize) -> bool {
+    T::SIGNED
+        && bytes[pos] == b'-'
+        && bytes.get(pos + 1).is_some_and(u8::is_ascii_digit)
+        && (pos == 0 || !bytes[pos - 1].is_ascii_digit())
+}
+
+/// Error at byte `pos` of `input`, with the line it is on.
+fn error_at(input: &str, pos: usize, expected: &str) -> ParseError {
+    let line_start = input[..pos].rfind('\n').map_or(0, |i| i + 1);
+    let line_end = input[pos..].find('\n').map_or(input.len(), |i| pos + i);
+    let line = input[..pos].matches('\n').count() + 1;
+    ParseError::at(
+        line,
+        &input[line_start..line_end],
+        pos - line_start,
+        expected,
+    )
+}
+
+/// Iterator returned by [`ints`].
+#[derive(Debug, Clone)]
+pub struct Ints<'a, T> {
+    input: &'a str,
+    pos: usize,
+    _marker: PhantomData<T>,
+}
+
+impl<T: Int> Iterator for Ints<'_, T> {
+    type Item = ParseResult<T>;
+
+    fn next(&mut self) -> Option<ParseResult<T>> {
+        let bytes = self.input.as_bytes();
+        let start = self.pos
+            + bytes[self.pos..]
+                .iter()
+                .enumerate()
+                .position(|(i, b)| b.is_ascii_digit() || is_sign::<T>(bytes, self.pos + i))?;
+        let negative = bytes[start] == b'-';
+        let digits_start = start + negative as usize;
+        let len = digit_run(&bytes[digits_start..]);
+        self.pos = digits_start + len;
+        let digits = &bytes[digits_start..self.pos];
+        Some(
+            magnitude(digits)
+                .and_then(|m| T::from_magnitude(negative, m))
+                .ok_or_else(|| error_at(self.input, start, "an integer in range")),
+        )
+    }
+}
+
+/// Every integer in `input`, whatever separates them. A `-` directly before
+/// a number makes it negative for signed types, unless it follows a digit.
+/// Unsigned types have no sign, so there `-3` is read as `3`.
+/// A number that does not fit in `T` is an error with its position, after
+/// which the iterator goes on with the next number. Does not allocate.
+/// # Examples
+/// ```
+/// use utils::ints;
+/// use utils::parse::ParseResult;
+///
+/// let values = ints::<usize>("[.##.] (3) (1,3) {3,5,4,7}").collect::<ParseResult<Vec<_>>>();
+/// assert_eq!(values, Ok(vec![3, 1, 3, 3, 5, 4, 7]));
+/// let values = ints::<i32>("x=-3, y=12..-4 1-3").collect::<ParseResult<Vec<_>>>();
+/// assert_eq!(values, Ok(vec![-3, 12, -4, 1, 3]));
+/// assert_eq!(ints::<u32>("x=-3").next(), Some(Ok(3)));
+///
+/// let err = ints::<u8>("1, 2\n(300)").nth(2).unwrap().unwrap_err();
+/// assert_eq!((err.line, err.column, err.snippet.as_str()), (2, 2, "300"));
+/// ```
+pub fn ints<T: Int>(input: &str) -> Ints<'_, T> {
+    Ints {
+        input,
+        pos: 0,
+        _marker: PhantomData,
+    }
+}
+
+/// Iterator returned by [`strict_ints`].
+#[derive(Debug, Clone)]
+pub struct StrictInts<'a, T> {
+    input: &'a str,
+    separators: &'a str,
+    pos: usize,
+    _marker: PhantomData<T>,
+}
+
+impl<T: Int> StrictInts<'_, T> {
+    fn is_separator(&self, b: u8) -> bool {
+        b.is_ascii_whitespace() || self.separators.as_bytes().contains(&b)
+    }
+
+    fn error(&mut self, pos: usize, expected: &str) -> ParseError {
+        self.pos = self.input.len();
+        error_at(self.input, pos, expected)
+    }
+}
+
+impl<T: Int> Iterator for StrictInts<'_, T> {
+    type Item = ParseResult<T>;
+
+    fn next(&mut self) -> Option<ParseResult<T>> {
+        let bytes = self.input.as_bytes();
+        let start = self.pos
+            + bytes[self.pos..]
+                .iter()
+                .position(|&b| !self.is_separator(b))?;
+        let negative = T::SIGNED && bytes[start] == b'-';
+        let digits_start = start + negative as usize;
+        let len = digit_run(&bytes[digits_start..]);
+        if len == 0 {
+            return Some(Err(self.error(start, "an integer")));
+        }
+        let end = digits_start + len;
+        if bytes.get(end).is_some_and(|&b| !self.is_separator(b)) {
+            return Some(Err(self.error(end, "a separator")));
+        }
+        self.pos = end;
+        Some(
+            magnitude(&bytes[digits_start..end])
+                .and_then(|m| T::from_magnitude(negative, m))
+                .ok_or_else(|| self.error(start, "an integer in range")),
+        )
+    }
+}
+
+/// Like [`ints`], but only whitespace and the characters in `separators` may
+/// appear between numbers. Anything else is reported with its position, after
+/// which the iterator stops.
+/// # Examples
+/// ```
+/// use utils::strict_ints;
+///
+/// let values = strict_ints::<i64>("3,-5\n4, 7", ",").collect::<Result<Vec<_>, _>>();
+/// assert_eq!(values, Ok(vec![3, -5, 4, 7]));
+///
+/// let err = strict_ints::<u8>("1 2\n3 x4", "").collect::<Result<Vec<_>, _>>().unwrap_err();
+/// assert_eq!((err.line, err.column, err.snippet.as_str()), (2, 3, "x4"));
+/// let err = strict_ints::<u8>("1 256", "").nth(1).unwrap().unwrap_err();
+/// assert_eq!(err.expected, "an integer in range");
+/// ```
+pub fn strict_ints<'a, T: Int>(input: &'a str, separators: &'a str) -> StrictInts<'a, T> {
+    StrictInts {
+        input,
+        separators,
+        pos: 0,
+        _marker: PhantomData,
+    }
+}
@@ -1,17 +1,25 @@
-use std::fmt::Debug;
+use std::fmt::{Debug, Display};
 
+use input::{Input, InputOptions};
+use results::{InputKind, PartResult, Status};
+
+pub mod crypt;
 pub mod direction;
+pub mod graph;
 pub mod grid;
+pub mod input;
+pub mod intern;
+pub mod ints;
+pub mod parse;
+pub mod results;
+pub mod scaling;
+pub mod sections;
+pub mod stream;
+pub mod trace;
 
-pub fn split_double_newline(input: &str) -> Vec<&str> {
-    let re = regex::Regex::new(r"\r?\n\r?\n").unwrap();
-    re.split(input).collect()
-}
-
-pub fn split_double_newline_once(input: &str) -> (&str, &str) {
-    let v = split_double_newline(input);
-    (v[0], v[1])
-}
+pub use ints::{ints, strict_ints};
+pub use sections::sections;
+pub use tracing;
 
 /// Extracts the last digit of a number
 /// # Examples
@@ -29,15 +37,27 @@ pub fn extract_last_digit(num: usize) -> usize {
         .unwrap() as usize
 }
 
-pub fn whitespaced_ints<T: num::Integer + std::str::FromStr>(input: &str) -> Vec<T> {
-    input
-        .split_whitespace()
-        .filter_map(|line| line.parse().ok())
-        .collect()
+/// What a part may return: the answer itself, or a `Result` whose error
+/// (e.g. a [`parse::ParseError`]) the harness prints before failing the check.
+pub trait PartOutput<T> {
+    fn into_answer(self) -> Result<T, String>;
+}
+
+impl<T> PartOutput<T> for T {
+    fn into_answer(self) -> Result<T, String> {
+        Ok(self)
+    }
+}
+
+impl<T, E: Display> PartOutput<T> for Result<T, E> {
+    fn into_answer(self) -> Result<T, String> {
+        self.map_err(|e| e.to_string())
+    }
 }
 
-fn answer<T: PartialEq + Debug>(
-    get: impl FnOnce() -> T,
+fn answer<'a, T: PartialEq + Debug, R: PartOutput<T>>(
+    prepare: impl FnOnce() -> Input<'a>,
+    solve: impl FnOnce(&str) -> R,
     actual: T,
     is_test: bool,
     part: u8,
@@ -45,10 +65,124 @@ fn answer<T: PartialEq + Debug>(
 ) {
     use std::io::{stderr, Write};
 
+    let input_kind = InputKind::from_is_test(is_test);
+    if !input_kind.is_enabled() {
+        return;
+    }
+    trace::init();
+    if results::is_recording() {
+        let _ = writeln!(
+            stderr().lock(),
+            "{}",
+            results::marker(day, part, input_kind)
+        );
+    }
+    let span = tracing::info_span!("part", day, part, input = %input_kind).entered();
+    let input = tracing::info_span!("prepare").in_scope(prepare);
+    for issue in input.issues.iter() {
+        if !issue.is_fatal() && !issue.is_unavailable() {
+            let _ = writeln!(
+                stderr().lock(),
+                "[{}] Part {} suspicious {} input: {}",
+                day,
+                part,
+                input_kind,
+                issue
+            );
+        }
+    }
+    if let Some(issue) = input.unavailable_issue() {
+        drop(span);
+        let _ = writeln!(
+            stderr().lock(),
+            "[{}] Part {} {}skipped: {}",
+            day,
+            part,
+            if is_test { "test " } else { "" },
+            issue
+        );
+        return;
+    }
+    if let Some(issue) = input.fatal_issue() {
+        drop(span);
+        if is_test {
+            let _ = writeln!(
+                stderr().lock(),
+                "[{}] Part {} test skipped: {}",
+                day,
+                part,
+                issue
+            );
+            return;
+        }
+        results::record(&PartResult {
+            day: day.to_string(),
+            part,
+            input: input_kind,
+            status: Status::Failed,
+            elapsed: std::time::Duration::ZERO,
+            answer: "error".to_string(),
+            expected: format!("{:?}", actual),
+        });
+        panic!(
+            "[{}] Part {} cannot run on the real input: {}",
+            day, part, issue
+        );
+    }
+
     let time = std::time::Instant::now();
-    let result = get();
+    let result = tracing::info_span!("solve")
+        .in_scope(|| std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| solve(&input.text))));
     let elapsed = time.elapsed();
+    let result = match result {
+        Ok(result) => result,
+        Err(panic) => {
+            if !input.issues.is_empty() {
+                let mut msg = format!(
+                    "[{}] Part {} panicked on {} input with these issues:\n",
+                    day, part, input_kind
+                );
+                for issue in &input.issues {
+                    msg += &format!("  - {}\n", issue);
+                }
+                let _ = stderr().lock().write_all(msg.as_bytes());
+            }
+            std::panic::resume_unwind(panic);
+        }
+    };
 
+    drop(span);
+    trace::flush();
+    let mut record = PartResult {
+        day: day.to_string(),
+        part,
+        input: input_kind,
+        status: Status::Failed,
+        elapsed,
+        answer: String::new(),
+        expected: format!("{:?}", actual),
+    };
+    let result = match result.into_answer() {
+        Ok(result) => result,
+        Err(error) => {
+            record.answer = "error".to_string();
+            results::record(&record);
+            let _ = writeln!(
+                stderr().lock(),
+                "[{}] Part {} failed on {} input:\n{}",
+                day,
+                part,
+                input_kind,
+                error
+            );
+            panic!("Part {} returned an error", part);
+        }
+    };
+    record.answer = format!("{:?}", result);
+    if result == actual {
+        record.status = Status::Passed;
+    }
+    results::record(&record);
     assert_eq!(result, actual);
 
     let msg = format!(
@@ -60,86 +194,136 @@ fn answer<T: PartialEq + Debug>(
     );
     let _ = stderr().lock().write_all(msg.as_bytes());
 }
-pub fn test_part1<T: PartialEq + Debug>(get: impl FnOnce() -> T, actual: T, day: &str) {
-    answer(get, actual, true, 1, day);
+pub fn test_part1<T: PartialEq + Debug, R: PartOutput<T>>(
+    get: impl FnOnce() -> R,
+    actual: T,
+    day: &str,
+) {
+    answer(Input::default, |_| get(), actual, true, 1, day);
 }
-pub fn answer_part1<T: PartialEq + Debug>(get: impl FnOnce() -> T, actual: T, day: &str) {
-    answer(get, actual, false, 1, day);
+pub fn answer_part1<T: PartialEq + Debug, R: PartOutput<T>>(
+    get: impl FnOnce() -> R,
+    actual: T,
+    day: &str,
+) {
+    answer(Input::default, |_| get(), actual, false, 1, day);
 }
-pub fn test_part2<T: PartialEq + Debug>(get: impl FnOnce() -> T, actual: T, day: &str) {
-    answer(get, actual, true, 2, day);
+pub fn test_part2<T: PartialEq + Debug, R: PartOutput<T>>(
+    get: impl FnOnce() -> R,
+    actual: T,
+    day: &str,
+) {
+    answer(Input::default, |_| get(), actual, true, 2, day);
 }
-pub fn answer_part2<T: PartialEq + Debug>(get: impl FnOnce() -> T, actual: T, day: &str) {
-    answer(get, actual, false, 2, day);
+pub fn answer_part2<T: PartialEq + Debug, R: PartOutput<T>>(
+    get: impl FnOnce() -> R,
+    actual: T,
+    day: &str,
+) {
+    answer(Input::default, |_| get(), actual, false, 2, day);
+}
+
+/// Used by the `part*_test!`/`part*_answer!` macros to run a part on an included input file.
+#[doc(hidden)]
+pub fn check<T: PartialEq + Debug, R: PartOutput<T>>(
+    raw: &str,
+    options: InputOptions,
+    solve: impl FnOnce(&str) -> R,
+    actual: T,
+    is_test: bool,
+    part: u8,
+    day: &str,
+) {
+    answer(
+        || input::prepare(raw, &options),
+        solve,
+        actual,
+        is_test,
+        part,
+        day,
+    );
 }
 
+/// Checks part 1 against `input_test.txt`. Pass `untrimmed` first to keep
+/// leading and trailing whitespace, or `grid` to warn about lines of different
+/// lengths. Pass an extra argument before the expected value to forward it to `part1`.
 #[macro_export]
 macro_rules! part1_test {
-    ($x:expr) => {{
-        utils::test_part1(
-            || part1(&include_str!("./input_test.txt").trim()),
-            $x,
-            env!("CARGO_PKG_NAME"),
-        );
-    }};
-    ($y: expr, $x:expr) => {{
-        utils::test_part1(
-            || part1(&include_str!("./input_test.txt").trim(), $y),
-            $x,
-            env!("CARGO_PKG_NAME"),
-        );
-    }};
+    (untrimmed, $($rest:tt)*) => {
+        utils::__part!("./input_test.txt", part1, true, 1, utils::input::InputOptions::untrimmed(), $($rest)*)
+    };
+    (grid, $($rest:tt)*) => {
+        utils::__part!("./input_test.txt", part1, true, 1, utils::input::InputOptions::grid(), $($rest)*)
+    };
+    ($($rest:tt)*) => {
+        utils::__part!("./input_test.txt", part1, true, 1, utils::input::InputOptions::default(), $($rest)*)
+    };
 }
 
+/// Checks part 1 against `input.txt`, see [`part1_test!`].
 #[macro_export]
 macro_rules! part1_answer {
-    ($x:expr) => {{
-        utils::answer_part1(
-            || part1(&include_str!("./input.txt").trim()),
-            $x,
-            env!("CARGO_PKG_NAME"),
-        );
-    }};
-    ($y: expr, $x:expr) => {{
-        utils::answer_part1(
-            || part1(&include_str!("./input.txt").trim(), $y),
-            $x,
-            env!("CARGO_PKG_NAME"),
-        );
-    }};
+    (untrimmed, $($rest:tt)*) => {
+        utils::__part!("./input.txt", part1, false, 1, utils::input::InputOptions::untrimmed(), $($rest)*)
+    };
+    (grid, $($rest:tt)*) => {
+        utils::__part!("./input.txt", part1, false, 1, utils::input::InputOptions::grid(), $($rest)*)
+    };
+    ($($rest:tt)*) => {
+        utils::__part!("./input.txt", part1, false, 1, utils::input::InputOptions::default(), $($rest)*)
+    };
 }
 
+/// Checks part 2 against `input_test.txt`, see [`part1_test!`].
 #[macro_export]
 macro_rules! part2_test {
-    ($x:expr) => {{
-        utils::test_part2(
-            || part2(&include_str!("./input_test.txt").trim()),
-            $x,
-            env!("CARGO_PKG_NAME"),
-        );
-    }};
-    ($y: expr, $x:expr) => {{
-        utils::test_part2(
-            || part2(&include_str!("./input_test.txt").trim(), $y),
-            $x,
-            env!("CARGO_PKG_NAME"),
-        );
-    }};
+    (untrimmed, $($rest:tt)*) => {
+        utils::__part!("./input_test.txt", part2, true, 2, utils::input::InputOptions::untrimmed(), $($rest)*)
+    };
+    (grid, $($rest:tt)*) => {
+        utils::__part!("./input_test.txt", part2, true, 2, utils::input::InputOptions::grid(), $($rest)*)
+    };
+    ($($rest:tt)*) => {
+        utils::__part!("./input_test.txt", part2, true, 2, utils::input::InputOptions::default(), $($rest)*)
+    };
 }
 
+/// Checks part 2 against `input.txt`, see [`part1_test!`].
 #[macro_export]
 macro_rules! part2_answer {
-    ($x:expr) => {{
-        utils::answer_part2(
-            || part2(&include_str!("./input.txt").trim()),
+    (untrimmed, $($rest:tt)*) => {
+        utils::__part!("./input.txt", part2, false, 2, utils::input::InputOptions::untrimmed(), $($rest)*)
+    };
+    (grid, $($rest:tt)*) => {
+        utils::__part!("./input.txt", part2, false, 2, utils::input::InputOptions::grid(), $($rest)*)
+    };
+    ($($rest:tt)*) => {
+        utils::__part!("./input.txt", part2, false, 2, utils::input::InputOptions::default(), $($rest)*)
+    };
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __part {
+    ($file:literal, $solve:ident, $is_test:expr, $part:expr, $options:expr, $x:expr) => {{
+        utils::check(
+            include_str!($file),
+            $options,
+            |input| $solve(input),
             $x,
+            $is_test,
+            $part,
             env!("CARGO_PKG_NAME"),
         );
     }};
-    ($y: expr, $x:expr) => {{
-        utils::answer_part2(
-            || part2(&include_str!("./input.txt").trim(), $y),
+    ($file:literal, $solve:ident, $is_test:expr, $part:expr, $options:expr, $y:expr, $x:expr) => {{
+        utils::check(
+            include_str!($file),
+            $options,
+            |input| $solve(input, $y),
             $x,
+            $is_test,
+            $part,
             env!("CARGO_PKG_NAME"),
         );
     }};
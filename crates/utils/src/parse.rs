@@ -0,0 +1,646 @@
+use std::fmt::Display;
+use std::str::FromStr;
+
+use itertools::Itertools;
+
+pub use utils_derive::{scan, FromLine};
+
+pub type ParseResult<T> = Result<T, ParseError>;
+
+/// A parse failure pointing at the offending part of the input.
+/// Line and column are 1-based, the column counts characters.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub line: usize,
+    pub column: usize,
+    pub snippet: String,
+    pub expected: String,
+    pub source_line: String,
+}
+
+impl ParseError {
+    /// Builds an error for the text starting at byte `start` of `source_line`.
+    /// The snippet is the token there: a run of alphanumeric characters, or a
+    /// single other character.
+    pub fn at(line: usize, source_line: &str, start: usize, expected: impl Into<String>) -> Self {
+        let rest = &source_line[start..];
+        let token_len = match rest.chars().next() {
+            Some(c) if c.is_alphanumeric() || c == '-' || c == '+' => rest
+                .char_indices()
+                .skip(1)
+                .find(|(_, c)| !c.is_alphanumeric())
+                .map_or(rest.len(), |(i, _)| i),
+            Some(c) => c.len_utf8(),
+            None => 0,
+        };
+        Self {
+            line,
+            column: source_line[..start].chars().count() + 1,
+            snippet: rest[..token_len].to_string(),
+            expected: expected.into(),
+            source_line: source_line.to_string(),
+        }
+    }
+
+    /// Shifts the line number, for errors from a section that does not start at line 1.
+    pub fn offset_lines(mut self, lines: usize) -> Self {
+        self.line += lines;
+        self
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let found = if self.snippet.is_empty() {
+            "end of line".to_string()
+        } else {
+            format!("{:?}", self.snippet)
+        };
+        writeln!(
+            f,
+            "line {}, column {}: expected {}, found {}",
+            self.line, self.column, self.expected, found
+        )?;
+        let gutter = " ".repeat(self.line.to_string().len());
+        writeln!(f, "{} |", gutter)?;
+        writeln!(f, "{} | {}", self.line, self.source_line)?;
+        write!(
+            f,
+            "{} | {}{}",
+            gutter,
+            " ".repeat(self.column - 1),
+            "^".repeat(self.snippet.chars().count().max(1))
+        )
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// Cursor over a single line of input.
+/// # Examples
+/// ```
+/// use utils::parse::Parser;
+///
+/// let mut p = Parser::new("12x5: 1 0 3", 1);
+/// let width = p.int::<usize>().unwrap();
+/// p.tag("x").unwrap();
+/// let height = p.int::<usize>().unwrap();
+/// p.tag(": ").unwrap();
+/// let counts = p.list(" ", |p| p.int::<usize>()).unwrap();
+/// p.end().unwrap();
+/// assert_eq!((width, height, counts), (12, 5, vec![1, 0, 3]));
+///
+/// let err = Parser::new("Lx6", 4).skip(1).int::<usize>().unwrap_err();
+/// assert_eq!((err.line, err.column, err.snippet.as_str()), (4, 2, "x6"));
+/// ```
+#[derive(Debug, Clone)]
+pub struct Parser<'a> {
+    line: &'a str,
+    line_no: usize,
+    pos: usize,
+    /// Parsing stops here, e.g. at the end of a [`Group`].
+    end: usize,
+}
+
+impl<'a> Parser<'a> {
+    /// `line_no` is the 1-based line number used in errors.
+    pub fn new(line: &'a str, line_no: usize) -> Self {
+        Self {
+            line,
+            line_no,
+            pos: 0,
+            end: line.len(),
+        }
+    }
+
+    pub fn line_no(&self) -> usize {
+        self.line_no
+    }
+
+    /// The unparsed remainder of the line.
+    pub fn remaining(&self) -> &'a str {
+        &self.line[self.pos..self.end]
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.pos == self.end
+    }
+
+    pub fn peek(&self) -> Option<char> {
+        self.remaining().chars().next()
+    }
+
+    pub fn error(&self, expected: impl Into<String>) -> ParseError {
+        self.error_at(self.pos, expected)
+    }
+
+    fn error_at(&self, pos: usize, expected: impl Into<String>) -> ParseError {
+        ParseError::at(self.line_no, self.line, pos, expected)
+    }
+
+    /// Skips `n` characters.
+    pub fn skip(&mut self, n: usize) -> &mut Self {
+        self.pos += self
+            .remaining()
+            .char_indices()
+            .nth(n)
+            .map_or(self.remaining().len(), |(i, _)| i);
+        self
+    }
+
+    pub fn skip_whitespace(&mut self) -> &mut Self {
+        let rest = self.remaining();
+        self.pos += rest.len() - rest.trim_start().len();
+        self
+    }
+
+    /// Consumes `tag` if the remainder starts with it.
+    pub fn eat(&mut self, tag: &str) -> bool {
+        if self.remaining().starts_with(tag) {
+            self.pos += tag.len();
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Expects the remainder to start with `tag`, e.g. a separator.
+    pub fn tag(&mut self, tag: &str) -> ParseResult<()> {
+        if self.eat(tag) {
+            Ok(())
+        } else {
+            Err(self.error(format!("{:?}", tag)))
+        }
+    }
+
+    /// Expects one of the given characters and returns it.
+    pub fn one_of(&mut self, chars: &[char]) -> ParseResult<char> {
+        match self.peek() {
+            Some(c) if chars.contains(&c) => {
+                self.pos += c.len_utf8();
+                Ok(c)
+            }
+            _ => Err(self.error(format!("one of {:?}", chars))),
+        }
+    }
+
+    /// Consumes characters while `f` holds and returns them, possibly empty.
+    pub fn take_while(&mut self, f: impl Fn(char) -> bool) -> &'a str {
+        let rest = self.remaining();
+        let len = rest.find(|c| !f(c)).unwrap_or(rest.len());
+        self.pos += len;
+        &rest[..len]
+    }
+
+    /// Consumes everything before `delimiter` without consuming the delimiter itself.
+    pub fn take_until(&mut self, delimiter: &str) -> ParseResult<&'a str> {
+        match self.remaining().find(delimiter) {
+            Some(len) => {
+                let taken = &self.remaining()[..len];
+                self.pos += len;
+                Ok(taken)
+            }
+            None => Err(self.error_at(self.end, format!("{:?}", delimiter))),
+        }
+    }
+
+    /// Consumes and returns the rest of the line.
+    pub fn rest(&mut self) -> &'a str {
+        let rest = self.remaining();
+        self.pos = self.end;
+        rest
+    }
+
+    /// A non-empty run of non-whitespace characters.
+    pub fn word(&mut self) -> ParseResult<&'a str> {
+        let start = self.pos;
+        let word = self.take_while(|c| !c.is_whitespace());
+        if word.is_empty() {
+            Err(self.error_at(start, "a word"))
+        } else {
+            Ok(word)
+        }
+    }
+
+    /// An optionally signed decimal integer.
+    pub fn int<T: FromStr>(&mut self) -> ParseResult<T> {
+        let start = self.pos;
+        let rest = self.remaining();
+        let sign = usize::from(rest.starts_with(['-', '+']));
+        let digits = rest[sign..]
+            .find(|c: char| !c.is_ascii_digit())
+            .unwrap_or(rest.len() - sign);
+        if digits == 0 {
+            return Err(self.error_at(start, "an integer"));
+        }
+        let value = rest[..sign + digits]
+            .parse()
+            .map_err(|_| self.error_at(start, "an integer in range"))?;
+        self.pos += sign + digits;
+        Ok(value)
+    }
+
+    /// Parses the next word with `FromStr`.
+    pub fn value<T: FromStr>(&mut self, expected: &str) -> ParseResult<T> {
+        let start = self.pos;
+        self.word()?
+            .parse()
+            .map_err(|_| self.error_at(start, expected))
+    }
+
+    /// One or more items separated by `separator`.
+    pub fn list<T>(
+        &mut self,
+        separator: &str,
+        mut item: impl FnMut(&mut Self) -> ParseResult<T>,
+    ) -> ParseResult<Vec<T>> {
+        let mut items = vec![item(self)?];
+        while self.eat(separator) {
+            items.push(item(self)?);
+        }
+        Ok(items)
+    }
+
+    /// `inner` surrounded by `open` and `close`, e.g. `delimited("(", ")", ...)`.
+    pub fn delimited<T>(
+        &mut self,
+        open: &str,
+        close: &str,
+        inner: impl FnOnce(&mut Self) -> ParseResult<T>,
+    ) -> ParseResult<T> {
+        self.tag(open)?;
+        let value = inner(self)?;
+        self.tag(close)?;
+        Ok(value)
+    }
+
+    /// Tries each alternative in order and keeps the first that succeeds.
+    /// If all fail, reports the error that got furthest, merging what was
+    /// expected when several alternatives stopped at the same column.
+    pub fn alt<T>(&mut self, alternatives: &[fn(&mut Self) -> ParseResult<T>]) -> ParseResult<T> {
+        let mut errors = vec![];
+        for alternative in alternatives {
+            let mut attempt = self.clone();
+            match alternative(&mut attempt) {
+                Ok(value) => {
+                    *self = attempt;
+                    return Ok(value);
+                }
+                Err(err) => errors.push(err),
+            }
+        }
+        let column = errors
+            .iter()
+            .map(|err| err.column)
+            .max()
+            .expect("No alternatives given");
+        let furthest = errors
+            .into_iter()
+            .filter(|err| err.column == column)
+            .collect::<Vec<_>>();
+        let expected = furthest
+            .iter()
+            .map(|err| err.expected.as_str())
+            .unique()
+            .join(" or ");
+        Err(ParseError {
+            expected,
+            ..furthest[0].clone()
+        })
+    }
+
+    /// Splits a `key<separator>value` line, returning the key and leaving the
+    /// parser at the start of the value.
+    pub fn key(&mut self, separator: &str) -> ParseResult<&'a str> {
+        let key = self.take_until(separator)?;
+        self.pos += separator.len();
+        Ok(key)
+    }
+
+    /// A bracketed group such as `[.##.]`, `(1,3)` or `{3,5,4,7}`, which may
+    /// contain nested groups. Reports unbalanced and mismatched delimiters.
+    /// # Examples
+    /// ```
+    /// use utils::parse::{Delimiter, Parser};
+    ///
+    /// let mut p = Parser::new("[.##.] (3) (1,3) {3,5,4,7}", 1);
+    /// let groups = p.groups().unwrap();
+    /// assert_eq!(groups.len(), 4);
+    /// assert_eq!((groups[0].delimiter, groups[0].inner), (Delimiter::Square, ".##."));
+    /// assert_eq!(groups[2].list::<usize>(",").unwrap(), [1, 3]);
+    ///
+    /// let nested = Parser::new("{a: [1, 2], b: ()}", 1).group().unwrap();
+    /// let inner = nested.parser().groups_anywhere().unwrap();
+    /// assert_eq!(inner.iter().map(|g| g.inner).collect::<Vec<_>>(), ["1, 2", ""]);
+    ///
+    /// let err = Parser::new("(1,3] {2}", 7).groups().unwrap_err();
+    /// assert_eq!((err.line, err.column, err.expected.as_str()), (7, 5, "\")\""));
+    /// let err = Parser::new("[.#", 1).groups().unwrap_err();
+    /// assert_eq!((err.column, err.expected.as_str()), (1, "a group closed by \"]\""));
+    /// let err = Parser::new("[.#] x", 1).groups().unwrap_err();
+    /// assert_eq!((err.column, err.snippet.as_str()), (6, "x"));
+    /// ```
+    pub fn group(&mut self) -> ParseResult<Group<'a>> {
+        let open = self.pos;
+        let Some(delimiter) = self.peek().and_then(Delimiter::from_open) else {
+            return Err(self.error("a group"));
+        };
+        let mut stack = vec![delimiter];
+        for (i, c) in self.remaining().char_indices().skip(1) {
+            if let Some(nested) = Delimiter::from_open(c) {
+                stack.push(nested);
+            } else if let Some(closed) = Delimiter::from_close(c) {
+                let expected = stack.pop().unwrap();
+                if closed != expected {
+                    let expected = format!("{:?}", expected.close().to_string());
+                    return Err(self.error_at(self.pos + i, expected));
+                }
+                if stack.is_empty() {
+                    let close = self.pos + i;
+                    self.pos = close + 1;
+                    return Ok(Group {
+                        delimiter,
+                        inner: &self.line[open + 1..close],
+                        parser: Parser {
+                            pos: open + 1,
+                            end: close,
+                            ..self.clone()
+                        },
+                    });
+                }
+            }
+        }
+        let expected = format!("a group closed by {:?}", delimiter.close().to_string());
+        Err(self.error_at(open, expected))
+    }
+
+    /// Whitespace separated groups up to the end, see [`Parser::group`].
+    pub fn groups(&mut self) -> ParseResult<Vec<Group<'a>>> {
+        let mut groups = vec![];
+        while !self.skip_whitespace().is_empty() {
+            groups.push(self.group()?);
+        }
+        Ok(groups)
+    }
+
+    /// Groups up to the end, skipping any text between them.
+    pub fn groups_anywhere(&mut self) -> ParseResult<Vec<Group<'a>>> {
+        let mut groups = vec![];
+        loop {
+            self.take_while(|c| Delimiter::from_open(c).is_none());
+            if self.is_empty() {
+                return Ok(groups);
+            }
+            groups.push(self.group()?);
+        }
+    }
+
+    /// Expects the whole line to have been consumed.
+    pub fn end(&self) -> ParseResult<()> {
+        if self.is_empty() {
+            Ok(())
+        } else {
+            Err(self.error("end of line"))
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Delimiter {
+    /// `(...)`
+    Round,
+    /// `[...]`
+    Square,
+    /// `{...}`
+    Curly,
+    /// `<...>`
+    Angle,
+}
+
+impl Delimiter {
+    pub fn from_open(c: char) -> Option<Self> {
+        match c {
+            '(' => Some(Self::Round),
+            '[' => Some(Self::Square),
+            '{' => Some(Self::Curly),
+            '<' => Some(Self::Angle),
+            _ => None,
+        }
+    }
+
+    pub fn from_close(c: char) -> Option<Self> {
+        match c {
+            ')' => Some(Self::Round),
+            ']' => Some(Self::Square),
+            '}' => Some(Self::Curly),
+            '>' => Some(Self::Angle),
+            _ => None,
+        }
+    }
+
+    pub fn open(self) -> char {
+        match self {
+            Self::Round => '(',
+            Self::Square => '[',
+            Self::Curly => '{',
+            Self::Angle => '<',
+        }
+    }
+
+    pub fn close(self) -> char {
+        match self {
+            Self::Round => ')',
+            Self::Square => ']',
+            Self::Curly => '}',
+            Self::Angle => '>',
+        }
+    }
+}
+
+/// A bracketed part of a line, see [`Parser::group`].
+#[derive(Debug, Clone)]
+pub struct Group<'a> {
+    pub delimiter: Delimiter,
+    /// The text between the delimiters.
+    pub inner: &'a str,
+    parser: Parser<'a>,
+}
+
+impl<'a> Group<'a> {
+    /// A parser over the group's contents that reports columns within the whole line.
+    pub fn parser(&self) -> Parser<'a> {
+        self.parser.clone()
+    }
+
+    /// Parses the contents as `separator`-separated values, e.g. `(1,3)`.
+    /// An empty group gives an empty list.
+    pub fn list<T: FromLine<'a>>(&self, separator: &str) -> ParseResult<Vec<T>> {
+        let mut p = self.parser();
+        if p.skip_whitespace().is_empty() {
+            return Ok(vec![]);
+        }
+        let items = p.list(separator, |p| {
+            p.skip_whitespace();
+            let item = T::parse_line(p)?;
+            p.skip_whitespace();
+            Ok(item)
+        })?;
+        p.end()?;
+        Ok(items)
+    }
+
+    /// An error pointing at the opening delimiter, e.g. for a group that is not allowed here.
+    pub fn error(&self, expected: impl Into<String>) -> ParseError {
+        self.parser.error_at(self.parser.pos - 1, expected)
+    }
+}
+
+/// Types that can be parsed from (part of) a line, usually via `#[derive(FromLine)]`.
+/// # Examples
+/// ```
+/// use utils::parse::FromLine;
+///
+/// #[derive(Debug, PartialEq, FromLine)]
+/// #[line("{x},{y}")]
+/// struct Point {
+///     x: i32,
+///     y: i32,
+/// }
+///
+/// #[derive(Debug, PartialEq, FromLine)]
+/// enum Turn {
+///     #[line("L{}")]
+///     Left(u32),
+///     #[line("R{}")]
+///     Right(u32),
+/// }
+///
+/// assert_eq!("3,-4".parse(), Ok(Point { x: 3, y: -4 }));
+/// assert_eq!("R12".parse(), Ok(Turn::Right(12)));
+///
+/// let err = "X12".parse::<Turn>().unwrap_err();
+/// assert_eq!(err.to_string().lines().next(), Some("line 1, column 1: expected \"L\" or \"R\", found \"X12\""));
+/// ```
+pub trait FromLine<'a>: Sized {
+    fn parse_line(p: &mut Parser<'a>) -> ParseResult<Self>;
+}
+
+macro_rules! from_line_int {
+    ($($t:ty),*) => {
+        $(
+            impl FromLine<'_> for $t {
+                fn parse_line(p: &mut Parser<'_>) -> ParseResult<Self> {
+                    p.int()
+                }
+            }
+        )*
+    };
+}
+
+from_line_int!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
+
+impl FromLine<'_> for char {
+    fn parse_line(p: &mut Parser<'_>) -> ParseResult<Self> {
+        let c = p.peek().ok_or_else(|| p.error("a character"))?;
+        p.skip(1);
+        Ok(c)
+    }
+}
+
+/// A non-empty run of alphanumeric characters, so it stops at separators.
+impl<'a> FromLine<'a> for &'a str {
+    fn parse_line(p: &mut Parser<'a>) -> ParseResult<Self> {
+        let start = p.clone();
+        let word = p.take_while(char::is_alphanumeric);
+        if word.is_empty() {
+            Err(start.error("a name"))
+        } else {
+            Ok(word)
+        }
+    }
+}
+
+/// See the `&str` impl.
+impl FromLine<'_> for String {
+    fn parse_line(p: &mut Parser<'_>) -> ParseResult<Self> {
+        <&str>::parse_line(p).map(str::to_string)
+    }
+}
+
+/// Parses a whole line as `T`, failing if anything is left over.
+/// For one-off patterns without a type, use [`scan!`] instead.
+/// # Examples
+/// ```
+/// use utils::parse::{from_line, scan};
+///
+/// assert_eq!(from_line::<u8>("42"), Ok(42));
+///
+/// let (width, height, rest): (usize, usize, &str) = scan!("12x5: 1 0 3", "{}x{}: {..}").unwrap();
+/// assert_eq!((width, height, rest), (12, 5, "1 0 3"));
+/// let name = scan!("aaa: bbb ccc", "{&str}: {..}").unwrap().0;
+/// assert_eq!(name, "aaa");
+/// assert!(scan!("12y5", "{u32}x{u32}").is_err());
+/// ```
+pub fn from_line<'a, T: FromLine<'a>>(line: &'a str) -> ParseResult<T> {
+    let mut parser = Parser::new(line, 1);
+    let value = T::parse_line(&mut parser)?;
+    parser.end()?;
+    Ok(value)
+}
+
+/// Parses every line with `f`, numbering lines from 1.
+/// # Examples
+/// ```
+/// use utils::parse::lines;
+///
+/// let parsed = lines("1-3\n5-9", |p| {
+///     let a = p.int::<u32>()?;
+///     p.tag("-")?;
+///     Ok((a, p.int::<u32>()?))
+/// });
+/// assert_eq!(parsed, Ok(vec![(1, 3), (5, 9)]));
+///
+/// let err = lines("1-3\n5_9", |p| {
+///     let a = p.int::<u32>()?;
+///     p.tag("-")?;
+///     Ok((a, p.int::<u32>()?))
+/// })
+/// .unwrap_err();
+/// assert_eq!(err.to_string(), "line 2, column 2: expected \"-\", found \"_\"\n  |\n2 | 5_9\n  |  ^");
+/// ```
+pub fn lines<'a, T>(
+    input: &'a str,
+    mut f: impl FnMut(&mut Parser<'a>) -> ParseResult<T>,
+) -> ParseResult<Vec<T>> {
+    let _span = tracing::info_span!("parse").entered();
+    input
+        .lines()
+        .enumerate()
+        .map(|(i, line)| {
+            let mut parser = Parser::new(line, i + 1);
+            let value = f(&mut parser)?;
+            parser.end()?;
+            Ok(value)
+        })
+        .collect()
+}
+
+/// Parses `key: values` lines, handing `f` a parser positioned after the separator.
+pub fn key_value_lines<'a, T>(
+    input: &'a str,
+    separator: &str,
+    mut f: impl FnMut(&mut Parser<'a>) -> ParseResult<T>,
+) -> ParseResult<Vec<(&'a str, T)>> {
+    let _span = tracing::info_span!("parse").entered();
+    input
+        .lines()
+        .enumerate()
+        .map(|(i, line)| {
+            let mut parser = Parser::new(line, i + 1);
+            let key = parser.key(separator)?;
+            let value = f(&mut parser)?;
+            parser.end()?;
+            Ok((key, value))
+        })
+        .collect()
+}
@@ -0,0 +1,210 @@
+use std::fmt::Display;
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::path::Path;
+use std::str::FromStr;
+use std::time::Duration;
+
+/// File that [`record`] appends part results to. Unset means nothing is recorded.
+pub const RESULTS_ENV: &str = "AOC_RESULTS";
+/// Restricts which checks run: `example`, `real` or unset for both.
+pub const INPUT_ENV: &str = "AOC_INPUT";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum InputKind {
+    Example,
+    Real,
+}
+
+impl InputKind {
+    pub fn from_is_test(is_test: bool) -> Self {
+        if is_test {
+            Self::Example
+        } else {
+            Self::Real
+        }
+    }
+
+    /// Whether checks on this input should run, according to [`INPUT_ENV`].
+    pub fn is_enabled(&self) -> bool {
+        match std::env::var(INPUT_ENV) {
+            Ok(v) => v.parse::<InputKind>().map_or(true, |kind| kind == *self),
+            Err(_) => true,
+        }
+    }
+
+    pub fn toggle(&self) -> Self {
+        match self {
+            Self::Example => Self::Real,
+            Self::Real => Self::Example,
+        }
+    }
+}
+
+impl Display for InputKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Example => write!(f, "example"),
+            Self::Real => write!(f, "real"),
+        }
+    }
+}
+
+impl FromStr for InputKind {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "example" => Ok(Self::Example),
+            "real" => Ok(Self::Real),
+            _ => Err(format!("Unknown input kind: {}", s)),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Status {
+    Passed,
+    Failed,
+}
+
+impl Display for Status {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Passed => write!(f, "passed"),
+            Self::Failed => write!(f, "failed"),
+        }
+    }
+}
+
+impl FromStr for Status {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "passed" => Ok(Self::Passed),
+            "failed" => Ok(Self::Failed),
+            _ => Err(format!("Unknown status: {}", s)),
+        }
+    }
+}
+
+/// The outcome of one `part*_test!`/`part*_answer!` check.
+/// # Examples
+/// ```
+/// use std::time::Duration;
+/// use utils::results::{InputKind, PartResult, Status};
+///
+/// let result = PartResult {
+///     day: "day4".to_string(),
+///     part: 2,
+///     input: InputKind::Real,
+///     status: Status::Failed,
+///     elapsed: Duration::from_micros(1500),
+///     answer: "\"a\\tb\"".to_string(),
+///     expected: "8727".to_string(),
+/// };
+/// let line = result.to_line();
+/// assert_eq!(line.split('\t').count(), 7);
+/// assert_eq!(line.parse::<PartResult>(), Ok(result));
+/// assert!("day4\t2\treal".parse::<PartResult>().is_err());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PartResult {
+    pub day: String,
+    pub part: u8,
+    pub input: InputKind,
+    pub status: Status,
+    pub elapsed: Duration,
+    pub answer: String,
+    pub expected: String,
+}
+
+impl PartResult {
+    /// Serializes the result as a single tab-separated line.
+    /// Answers are `Debug` formatted, so they never contain raw tabs or newlines.
+    pub fn to_line(&self) -> String {
+        format!(
+            "{}\t{}\t{}\t{}\t{}\t{}\t{}",
+            self.day,
+            self.part,
+            self.input,
+            self.status,
+            self.elapsed.as_nanos(),
+            self.answer,
+            self.expected
+        )
+    }
+}
+
+impl FromStr for PartResult {
+    type Err = String;
+
+    fn from_str(line: &str) -> Result<Self, Self::Err> {
+        let fields = line.split('\t').collect::<Vec<_>>();
+        let [day, part, input, status, elapsed, answer, expected] = fields[..] else {
+            return Err(format!("Expected 7 fields, got {}", fields.len()));
+        };
+        Ok(Self {
+            day: day.to_string(),
+            part: part
+                .parse()
+                .map_err(|_| format!("Invalid part: {}", part))?,
+            input: input.parse()?,
+            status: status.parse()?,
+            elapsed: Duration::from_nanos(
+                elapsed
+                    .parse()
+                    .map_err(|_| format!("Invalid elapsed time: {}", elapsed))?,
+            ),
+            answer: answer.to_string(),
+            expected: expected.to_string(),
+        })
+    }
+}
+
+/// Appends the result to the file named by [`RESULTS_ENV`], if set.
+pub fn record(result: &PartResult) {
+    let Ok(path) = std::env::var(RESULTS_ENV) else {
+        return;
+    };
+    let file = OpenOptions::new().create(true).append(true).open(&path);
+    if let Ok(mut file) = file {
+        let _ = writeln!(file, "{}", result.to_line());
+    }
+}
+
+/// Whether part results are being recorded for a runner such as `aoc tui`.
+pub fn is_recording() -> bool {
+    std::env::var_os(RESULTS_ENV).is_some()
+}
+
+/// Reads all results from a file written by [`record`], skipping malformed lines.
+pub fn load(path: impl AsRef<Path>) -> Vec<PartResult> {
+    std::fs::read_to_string(path)
+        .map(|content| content.lines().filter_map(|l| l.parse().ok()).collect())
+        .unwrap_or_default()
+}
+
+/// Line written to stderr before a part runs while recording, so runners can
+/// attribute the output that follows to that part.
+pub fn marker(day: &str, part: u8, input: InputKind) -> String {
+    format!("==> [{}] Part {} ({})", day, part, input)
+}
+
+/// Parses a line produced by [`marker`] into its part and input kind.
+/// # Examples
+/// ```
+/// use utils::results::{marker, parse_marker, InputKind};
+///
+/// let line = marker("day11", 2, InputKind::Example);
+/// assert_eq!(parse_marker(&line), Some((2, InputKind::Example)));
+/// assert_eq!(parse_marker("[day11] Part 2 (example)"), None);
+/// assert_eq!(parse_marker("==> [day11] Part x (real)"), None);
+/// ```
+pub fn parse_marker(line: &str) -> Option<(u8, InputKind)> {
+    let rest = line.strip_prefix("==> [")?;
+    let (_, rest) = rest.split_once("] Part ")?;
+    let (part, input) = rest.split_once(" (")?;
+    Some((part.parse().ok()?, input.strip_suffix(')')?.parse().ok()?))
+}
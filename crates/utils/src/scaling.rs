@@ -0,0 +1,204 @@
+use std::fmt::Display;
+use std::time::{Duration, Instant};
+
+/// Enables the `part*_scaling!` measurements when set.
+pub const SCALING_ENV: &str = "AOC_SCALING";
+
+const REPETITIONS: usize = 3;
+
+#[derive(Debug, Clone, Copy)]
+pub struct Sample {
+    pub size: usize,
+    pub elapsed: Duration,
+}
+
+#[derive(Debug, Clone)]
+pub struct ScalingReport {
+    pub samples: Vec<Sample>,
+    /// Slope of the least-squares fit of `ln(time)` over `ln(size)`,
+    /// i.e. `k` in `time ~ size^k`. `None` with fewer than two distinct sizes.
+    pub exponent: Option<f64>,
+}
+
+impl ScalingReport {
+    pub fn complexity(&self) -> &'static str {
+        let Some(exponent) = self.exponent else {
+            return "unknown, needs two distinct sizes";
+        };
+        match exponent {
+            e if e < 0.5 => "sub-linear",
+            e if e < 1.3 => "near-linear",
+            e if e < 1.7 => "between linear and quadratic",
+            e if e < 2.5 => "quadratic",
+            e if e < 3.5 => "cubic",
+            _ => "worse than cubic",
+        }
+    }
+}
+
+impl Display for ScalingReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for sample in &self.samples {
+            writeln!(f, "  n = {:>10}: {:>12.2?}", sample.size, sample.elapsed)?;
+        }
+        match self.exponent {
+            Some(exponent) => write!(f, "  time ~ n^{:.2} ({})", exponent, self.complexity()),
+            None => write!(f, "  time ~ n^? ({})", self.complexity()),
+        }
+    }
+}
+
+/// Fits `time ~ size^k` to the samples and returns `k`, or `None` if there
+/// are fewer than two distinct sizes to fit a slope to.
+/// # Examples
+/// ```
+/// use std::time::Duration;
+/// use utils::scaling::{fit_exponent, Sample};
+///
+/// let samples = [10, 20, 40, 80].map(|size| Sample {
+///     size,
+///     elapsed: Duration::from_nanos((size * size) as u64),
+/// });
+/// assert!((fit_exponent(&samples).unwrap() - 2.0).abs() < 1e-9);
+/// assert_eq!(fit_exponent(&samples[..1]), None);
+/// assert_eq!(fit_exponent(&[samples[1], samples[1]]), None);
+/// ```
+pub fn fit_exponent(samples: &[Sample]) -> Option<f64> {
+    let first = samples.first()?.size;
+    if samples.iter().all(|s| s.size == first) {
+        return None;
+    }
+    let points = samples
+        .iter()
+        .map(|s| ((s.size as f64).ln(), s.elapsed.as_secs_f64().max(1e-9).ln()))
+        .collect::<Vec<_>>();
+    let n = points.len() as f64;
+    let mean_x = points.iter().map(|p| p.0).sum::<f64>() / n;
+    let mean_y = points.iter().map(|p| p.1).sum::<f64>() / n;
+    let covariance = points
+        .iter()
+        .map(|p| (p.0 - mean_x) * (p.1 - mean_y))
+        .sum::<f64>();
+    let variance = points.iter().map(|p| (p.0 - mean_x).powi(2)).sum::<f64>();
+    Some(covariance / variance)
+}
+
+/// Times `solve` on `generate(size)` for every size, keeping the best of a few
+/// runs to reduce noise. Input generation is not timed.
+pub fn measure<T>(
+    solve: impl Fn(&str) -> T,
+    generate: impl Fn(usize) -> String,
+    sizes: &[usize],
+) -> ScalingReport {
+    let samples = sizes
+        .iter()
+        .map(|&size| {
+            let _span = tracing::info_span!("size", size).entered();
+            let input = generate(size);
+            let elapsed = (0..REPETITIONS)
+                .map(|_| {
+                    let time = Instant::now();
+                    std::hint::black_box(solve(std::hint::black_box(&input)));
+                    time.elapsed()
+                })
+                .min()
+                .unwrap();
+            Sample { size, elapsed }
+        })
+        .collect::<Vec<_>>();
+    let exponent = fit_exponent(&samples);
+    ScalingReport { samples, exponent }
+}
+
+/// Used by the `part*_scaling!` macros. Only measures if [`SCALING_ENV`] is set.
+#[doc(hidden)]
+pub fn run<T>(
+    solve: impl Fn(&str) -> T,
+    generate: impl Fn(usize) -> String,
+    sizes: &[usize],
+    part: u8,
+    day: &str,
+) {
+    if std::env::var_os(SCALING_ENV).is_none() {
+        return;
+    }
+    crate::trace::init();
+    let report =
+        tracing::info_span!("scaling", day, part).in_scope(|| measure(solve, generate, sizes));
+    crate::trace::flush();
+    eprintln!("[{}] Part {} scaling:\n{}", day, part, report);
+}
+
+/// Small xorshift generator, so input generators are reproducible without extra dependencies.
+#[derive(Debug, Clone)]
+pub struct Rng(u64);
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        Self(seed.max(1))
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+
+    /// A number in `0..bound`. Panics if `bound` is zero.
+    pub fn below(&mut self, bound: u64) -> u64 {
+        assert!(bound > 0, "Rng::below needs a positive bound");
+        self.next_u64() % bound
+    }
+
+    pub fn chance(&mut self, numerator: u64, denominator: u64) -> bool {
+        self.below(denominator) < numerator
+    }
+}
+
+/// Measures how part 1 scales on inputs from `$generate(size)` when `AOC_SCALING` is set.
+/// Pass an extra argument before the generator to forward it to `part1`.
+#[macro_export]
+macro_rules! part1_scaling {
+    ($generate:expr, $sizes:expr) => {
+        utils::scaling::run(
+            |input| part1(input),
+            $generate,
+            &$sizes,
+            1,
+            env!("CARGO_PKG_NAME"),
+        )
+    };
+    ($y:expr, $generate:expr, $sizes:expr) => {
+        utils::scaling::run(
+            |input| part1(input, $y),
+            $generate,
+            &$sizes,
+            1,
+            env!("CARGO_PKG_NAME"),
+        )
+    };
+}
+
+/// Measures how part 2 scales, see [`part1_scaling!`].
+#[macro_export]
+macro_rules! part2_scaling {
+    ($generate:expr, $sizes:expr) => {
+        utils::scaling::run(
+            |input| part2(input),
+            $generate,
+            &$sizes,
+            2,
+            env!("CARGO_PKG_NAME"),
+        )
+    };
+    ($y:expr, $generate:expr, $sizes:expr) => {
+        utils::scaling::run(
+            |input| part2(input, $y),
+            $generate,
+            &$sizes,
+            2,
+            env!("CARGO_PKG_NAME"),
+        )
+    };
+}
@@ -0,0 +1,165 @@
+use crate::parse::{self, ParseError, ParseResult, Parser};
+
+/// A run of non-blank lines, borrowed from the input.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Section<'a> {
+    /// The section without its final line ending.
+    pub text: &'a str,
+    /// 1-based line number of the first line within the whole input.
+    pub line: usize,
+}
+
+impl<'a> Section<'a> {
+    pub fn lines(&self) -> std::str::Lines<'a> {
+        self.text.lines()
+    }
+
+    /// Splits off a header line ending in `:`, e.g. `0:` above a shape,
+    /// returning the label without the colon and the rest of the section.
+    pub fn header(&self) -> Option<(&'a str, Section<'a>)> {
+        let (first, rest) = self.text.split_once('\n').unwrap_or((self.text, ""));
+        let label = first.trim_end().strip_suffix(':')?;
+        Some((
+            label,
+            Section {
+                text: rest,
+                line: self.line + 1,
+            },
+        ))
+    }
+
+    /// Like [`parse::lines`], with line numbers counted from the start of the input.
+    pub fn parse_lines<T>(
+        &self,
+        f: impl FnMut(&mut Parser<'a>) -> ParseResult<T>,
+    ) -> ParseResult<Vec<T>> {
+        parse::lines(self.text, f).map_err(|e| e.offset_lines(self.line - 1))
+    }
+
+    /// Applies `f` to every line, e.g. with [`scan!`](crate::parse::scan) or
+    /// [`from_line`](parse::from_line). Errors are assumed to report line 1
+    /// and are moved to the line's position in the input.
+    pub fn map_lines<T>(
+        &self,
+        mut f: impl FnMut(&'a str) -> ParseResult<T>,
+    ) -> ParseResult<Vec<T>> {
+        let _span = tracing::info_span!("parse").entered();
+        self.lines()
+            .enumerate()
+            .map(|(i, line)| f(line).map_err(|e| e.offset_lines(self.line - 1 + i)))
+            .collect()
+    }
+}
+
+/// Iterator returned by [`sections`].
+#[derive(Debug, Clone)]
+pub struct Sections<'a> {
+    rest: &'a str,
+    line: usize,
+}
+
+/// Splits off the first line, returning it without its line ending and the rest.
+fn split_line(text: &str) -> Option<(&str, &str)> {
+    if text.is_empty() {
+        None
+    } else {
+        Some(text.split_once('\n').unwrap_or((text, "")))
+    }
+}
+
+impl<'a> Iterator for Sections<'a> {
+    type Item = Section<'a>;
+
+    fn next(&mut self) -> Option<Section<'a>> {
+        loop {
+            let (line, rest) = split_line(self.rest)?;
+            if !line.trim().is_empty() {
+                break;
+            }
+            self.rest = rest;
+            self.line += 1;
+        }
+        let start = self.rest;
+        let first_line = self.line + 1;
+        let mut len = 0;
+        while let Some((line, rest)) = split_line(self.rest) {
+            if line.trim().is_empty() {
+                break;
+            }
+            len = start.len() - rest.len();
+            self.rest = rest;
+            self.line += 1;
+        }
+        Some(Section {
+            text: start[..len].trim_end_matches(['\n', '\r']),
+            line: first_line,
+        })
+    }
+}
+
+/// Splits the input into sections separated by blank lines, without allocating.
+/// Both `\n` and `\r\n` line endings work, and lines with only whitespace count as blank.
+/// # Examples
+/// ```
+/// use utils::sections;
+///
+/// let input = "0:\r\n#.\r\n\r\n\r\n1:\r\n.#\r\n\r\n4x4: 1 2\r\n";
+/// let sections = sections(input).collect::<Vec<_>>();
+/// assert_eq!(sections.len(), 3);
+/// assert_eq!((sections[1].text, sections[1].line), ("1:\r\n.#", 5));
+///
+/// let (label, shape) = sections[1].header().unwrap();
+/// assert_eq!((label, shape.text, shape.line), ("1", ".#", 6));
+/// assert_eq!(sections[2].lines().collect::<Vec<_>>(), ["4x4: 1 2"]);
+/// ```
+pub fn sections(input: &str) -> Sections<'_> {
+    Sections {
+        rest: input,
+        line: 0,
+    }
+}
+
+/// Splits the input into exactly `N` sections, for destructuring.
+/// # Examples
+/// ```
+/// use utils::sections::exactly;
+///
+/// let [ranges, ids] = exactly("3-5\n10-14\n\n1\n5\n").unwrap();
+/// assert_eq!((ranges.text, ids.text, ids.line), ("3-5\n10-14", "1\n5", 4));
+///
+/// let err = exactly::<3>("3-5\n\n1").unwrap_err();
+/// assert_eq!(err.to_string().lines().next(), Some("line 3, column 2: expected 3 sections, found end of line"));
+/// assert!(exactly::<1>("3-5\n\n1").is_err());
+/// ```
+pub fn exactly<const N: usize>(input: &str) -> ParseResult<[Section<'_>; N]> {
+    let _span = tracing::info_span!("parse").entered();
+    let mut iter = sections(input);
+    let mut found = [Section { text: "", line: 0 }; N];
+    let mut count = 0;
+    for (slot, section) in found.iter_mut().zip(iter.by_ref()) {
+        *slot = section;
+        count += 1;
+    }
+    let expected = format!("{} section{}", N, if N == 1 { "" } else { "s" });
+    if let Some(extra) = iter.next() {
+        let first = extra.lines().next().unwrap_or_default();
+        let expected = format!("end of input after {}", expected);
+        return Err(ParseError::at(extra.line, first, 0, expected));
+    }
+    if count < N {
+        let (line, source_line) = match count.checked_sub(1).map(|i| found[i]) {
+            Some(last) => (
+                last.line + last.text.lines().count() - 1,
+                last.text.lines().last().unwrap_or_default(),
+            ),
+            None => (1, ""),
+        };
+        return Err(ParseError::at(
+            line,
+            source_line,
+            source_line.len(),
+            expected,
+        ));
+    }
+    Ok(found)
+}
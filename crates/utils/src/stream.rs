@@ -0,0 +1,336 @@
+use std::fmt::Display;
+use std::fs::File;
+use std::io::{self, BufRead, BufReader};
+use std::path::Path;
+use std::time::Instant;
+
+use memmap2::Mmap;
+
+use crate::parse::{FromLine, ParseError, ParseResult, Parser};
+
+/// Path of a (possibly huge) input file for the `part*_stream!` macros.
+pub const STREAM_ENV: &str = "AOC_STREAM";
+
+#[derive(Debug)]
+pub enum StreamError {
+    Io(io::Error),
+    Parse(ParseError),
+}
+
+impl Display for StreamError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Io(err) => write!(f, "could not read input: {}", err),
+            Self::Parse(err) => write!(f, "{}", err),
+        }
+    }
+}
+
+impl std::error::Error for StreamError {}
+
+impl From<io::Error> for StreamError {
+    fn from(err: io::Error) -> Self {
+        Self::Io(err)
+    }
+}
+
+impl From<ParseError> for StreamError {
+    fn from(err: ParseError) -> Self {
+        Self::Parse(err)
+    }
+}
+
+enum Source<'a> {
+    Text {
+        bytes: &'a [u8],
+        pos: usize,
+    },
+    Mapped {
+        map: Mmap,
+        pos: usize,
+    },
+    Buffered {
+        reader: Box<dyn BufRead + 'a>,
+        buf: String,
+    },
+}
+
+/// Line-by-line input that does not need to fit in memory as a `String`.
+/// Lines are handed out without their line ending, `\r\n` included.
+pub struct InputStream<'a> {
+    source: Source<'a>,
+    line_no: usize,
+}
+
+/// Splits the next line off `bytes[*pos..]`.
+fn split_line<'b>(bytes: &'b [u8], pos: &mut usize) -> io::Result<Option<&'b str>> {
+    let Some(rest) = bytes.get(*pos..).filter(|rest| !rest.is_empty()) else {
+        return Ok(None);
+    };
+    let (line, len) = match rest.iter().position(|&b| b == b'\n') {
+        Some(i) => (&rest[..i], i + 1),
+        None => (rest, rest.len()),
+    };
+    *pos += len;
+    let line = line.strip_suffix(b"\r").unwrap_or(line);
+    std::str::from_utf8(line)
+        .map(Some)
+        .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))
+}
+
+impl<'a> InputStream<'a> {
+    /// Streams over text that is already in memory, e.g. from `include_str!`.
+    pub fn from_text(text: &'a str) -> Self {
+        Self {
+            source: Source::Text {
+                bytes: text.as_bytes(),
+                pos: 0,
+            },
+            line_no: 0,
+        }
+    }
+
+    pub fn from_reader(reader: impl BufRead + 'a) -> Self {
+        Self {
+            source: Source::Buffered {
+                reader: Box::new(reader),
+                buf: String::new(),
+            },
+            line_no: 0,
+        }
+    }
+
+    /// Memory-maps regular files and falls back to buffered reading for
+    /// anything else, such as pipes.
+    pub fn open(path: impl AsRef<Path>) -> io::Result<InputStream<'static>> {
+        let file = File::open(path)?;
+        if file.metadata()?.is_file() {
+            // SAFETY: the input is only read, and is not expected to be modified while solving.
+            let map = unsafe { Mmap::map(&file)? };
+            Ok(InputStream {
+                source: Source::Mapped { map, pos: 0 },
+                line_no: 0,
+            })
+        } else {
+            Ok(InputStream::from_reader(BufReader::new(file)))
+        }
+    }
+
+    /// 1-based number of the line last returned.
+    pub fn line_no(&self) -> usize {
+        self.line_no
+    }
+
+    pub fn next_line(&mut self) -> io::Result<Option<&str>> {
+        let line = match &mut self.source {
+            Source::Text { bytes, pos } => split_line(bytes, pos)?,
+            Source::Mapped { map, pos } => split_line(map, pos)?,
+            Source::Buffered { reader, buf } => {
+                buf.clear();
+                if reader.read_line(buf)? == 0 {
+                    None
+                } else {
+                    let line = buf.strip_suffix('\n').unwrap_or(buf);
+                    Some(line.strip_suffix('\r').unwrap_or(line))
+                }
+            }
+        };
+        if line.is_some() {
+            self.line_no += 1;
+        }
+        Ok(line)
+    }
+
+    /// Parses the next line with `f`, which has to consume all of it.
+    /// Returns `None` at the end of the input.
+    pub fn parse_line<T>(
+        &mut self,
+        f: impl FnOnce(&mut Parser<'_>) -> ParseResult<T>,
+    ) -> Result<Option<T>, StreamError> {
+        let line_no = self.line_no + 1;
+        let Some(line) = self.next_line()? else {
+            return Ok(None);
+        };
+        let mut p = Parser::new(line, line_no);
+        let value = f(&mut p)?;
+        p.end()?;
+        Ok(Some(value))
+    }
+
+    /// Parses every remaining line with `f`, like [`crate::parse::lines`].
+    /// Values cannot borrow from the line. For `FromLine` types, see [`InputStream::values`].
+    /// # Examples
+    /// ```
+    /// use utils::stream::InputStream;
+    ///
+    /// let mut stream = InputStream::from_reader("3-5\r\n10-14\r\n\r\n1\r\n5x\r\n".as_bytes());
+    /// let ranges = stream
+    ///     .parse_section(|p| {
+    ///         let start = p.int::<u64>()?;
+    ///         p.tag("-")?;
+    ///         Ok((start, p.int::<u64>()?))
+    ///     })
+    ///     .collect::<Result<Vec<_>, _>>()
+    ///     .unwrap();
+    /// assert_eq!(ranges, [(3, 5), (10, 14)]);
+    ///
+    /// let mut ids = stream.values::<u64>();
+    /// assert_eq!(ids.next().unwrap().unwrap(), 1);
+    /// let err = ids.next().unwrap().unwrap_err();
+    /// assert_eq!(err.to_string().lines().next(), Some("line 5, column 2: expected end of line, found \"x\""));
+    /// ```
+    pub fn parse_lines<T, F>(&mut self, f: F) -> ParseLines<'_, 'a, F>
+    where
+        F: FnMut(&mut Parser<'_>) -> ParseResult<T>,
+    {
+        ParseLines {
+            stream: self,
+            f,
+            section: false,
+            started: false,
+            done: false,
+        }
+    }
+
+    /// Parses every remaining line as a `T`, e.g. `stream.values::<u64>()`.
+    pub fn values<T>(&mut self) -> ParseLines<'_, 'a, impl FnMut(&mut Parser<'_>) -> ParseResult<T>>
+    where
+        T: for<'l> FromLine<'l>,
+    {
+        self.parse_lines(|p| T::parse_line(p))
+    }
+
+    /// Parses the lines of the next section as `T`, see [`InputStream::parse_section`].
+    pub fn section_values<T>(
+        &mut self,
+    ) -> ParseLines<'_, 'a, impl FnMut(&mut Parser<'_>) -> ParseResult<T>>
+    where
+        T: for<'l> FromLine<'l>,
+    {
+        self.parse_section(|p| T::parse_line(p))
+    }
+
+    /// Like [`InputStream::parse_lines`], but stops after the next blank line,
+    /// so sections can be read one after another. Leading blank lines are skipped.
+    pub fn parse_section<T, F>(&mut self, f: F) -> ParseLines<'_, 'a, F>
+    where
+        F: FnMut(&mut Parser<'_>) -> ParseResult<T>,
+    {
+        ParseLines {
+            stream: self,
+            f,
+            section: true,
+            started: false,
+            done: false,
+        }
+    }
+}
+
+/// Iterator returned by [`InputStream::parse_lines`] and [`InputStream::parse_section`].
+pub struct ParseLines<'s, 'a, F> {
+    stream: &'s mut InputStream<'a>,
+    f: F,
+    section: bool,
+    started: bool,
+    done: bool,
+}
+
+impl<T, F> Iterator for ParseLines<'_, '_, F>
+where
+    F: FnMut(&mut Parser<'_>) -> ParseResult<T>,
+{
+    type Item = Result<T, StreamError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while !self.done {
+            let line_no = self.stream.line_no + 1;
+            let line = match self.stream.next_line() {
+                Ok(Some(line)) => line,
+                Ok(None) => break,
+                Err(err) => {
+                    self.done = true;
+                    return Some(Err(err.into()));
+                }
+            };
+            if self.section && line.trim().is_empty() {
+                if self.started {
+                    self.done = true;
+                }
+                continue;
+            }
+            self.started = true;
+            let mut p = Parser::new(line, line_no);
+            return Some(
+                (self.f)(&mut p)
+                    .and_then(|value| p.end().map(|_| value))
+                    .map_err(StreamError::from),
+            );
+        }
+        self.done = true;
+        None
+    }
+}
+
+/// Used by the `part*_stream!` macros. Only runs if [`STREAM_ENV`] is set.
+#[doc(hidden)]
+pub fn run<T: Display, E: Display>(
+    solve: impl FnOnce(&mut InputStream<'static>) -> Result<T, E>,
+    part: u8,
+    day: &str,
+) {
+    let Some(path) = std::env::var_os(STREAM_ENV) else {
+        return;
+    };
+    let mut stream = match InputStream::open(&path) {
+        Ok(stream) => stream,
+        Err(err) => panic!("Could not open {}: {}", path.to_string_lossy(), err),
+    };
+    crate::trace::init();
+    let span = tracing::info_span!("part", day, part, input = "stream").entered();
+    let time = Instant::now();
+    let result = tracing::info_span!("solve").in_scope(|| solve(&mut stream));
+    let elapsed = time.elapsed();
+    drop(span);
+    crate::trace::flush();
+    match result {
+        Ok(answer) => eprintln!(
+            "[{}] Part {} on {}: {} Elapsed: {:.2?}",
+            day,
+            part,
+            path.to_string_lossy(),
+            answer,
+            elapsed
+        ),
+        Err(err) => panic!(
+            "[{}] Part {} failed on {}:\n{}",
+            day,
+            part,
+            path.to_string_lossy(),
+            err
+        ),
+    }
+}
+
+/// Runs `part1_stream` on the file in `AOC_STREAM` when it is set.
+/// `part1_stream` takes an [`InputStream`] and returns a `Result`.
+/// Pass an extra argument to forward it to `part1_stream`.
+#[macro_export]
+macro_rules! part1_stream {
+    () => {
+        utils::stream::run(|stream| part1_stream(stream), 1, env!("CARGO_PKG_NAME"))
+    };
+    ($y:expr) => {
+        utils::stream::run(|stream| part1_stream(stream, $y), 1, env!("CARGO_PKG_NAME"))
+    };
+}
+
+/// Runs `part2_stream` on the file in `AOC_STREAM`, see [`part1_stream!`].
+#[macro_export]
+macro_rules! part2_stream {
+    () => {
+        utils::stream::run(|stream| part2_stream(stream), 2, env!("CARGO_PKG_NAME"))
+    };
+    ($y:expr) => {
+        utils::stream::run(|stream| part2_stream(stream, $y), 2, env!("CARGO_PKG_NAME"))
+    };
+}
@@ -0,0 +1,60 @@
+use std::io::IsTerminal;
+use std::sync::{Mutex, OnceLock};
+
+use tracing_chrome::{ChromeLayerBuilder, FlushGuard};
+use tracing_subscriber::fmt::format::FmtSpan;
+use tracing_subscriber::prelude::*;
+use tracing_subscriber::EnvFilter;
+
+/// Level filter for events printed to stderr, in `EnvFilter` syntax (e.g. `debug` or `day7=trace`).
+pub const LOG_ENV: &str = "AOC_LOG";
+/// File to write a chrome-trace JSON timeline of all spans to.
+pub const CHROME_ENV: &str = "AOC_TRACE";
+
+static CHROME_GUARD: OnceLock<Mutex<Option<FlushGuard>>> = OnceLock::new();
+
+/// Installs the global subscriber according to [`LOG_ENV`] and [`CHROME_ENV`].
+/// Does nothing if neither is set or if it has already been called.
+pub fn init() {
+    CHROME_GUARD.get_or_init(|| {
+        let filter = std::env::var(LOG_ENV)
+            .ok()
+            .map(|_| EnvFilter::from_env(LOG_ENV));
+        let chrome_path = std::env::var_os(CHROME_ENV);
+        if filter.is_none() && chrome_path.is_none() {
+            return Mutex::new(None);
+        }
+        let fmt = filter.map(|filter| {
+            tracing_subscriber::fmt::layer()
+                .with_writer(std::io::stderr)
+                .with_ansi(std::io::stderr().is_terminal())
+                .with_span_events(FmtSpan::CLOSE)
+                .with_filter(filter)
+        });
+        let (chrome, guard) = match chrome_path {
+            Some(path) => {
+                let (layer, guard) = ChromeLayerBuilder::new()
+                    .file(path)
+                    .include_args(true)
+                    .build();
+                (Some(layer), Some(guard))
+            }
+            None => (None, None),
+        };
+        let _ = tracing_subscriber::registry()
+            .with(fmt)
+            .with(chrome)
+            .try_init();
+        Mutex::new(guard)
+    });
+}
+
+/// Asks the chrome-trace writer to flush the events it has buffered.
+/// Does nothing unless [`CHROME_ENV`] is set.
+pub fn flush() {
+    if let Some(guard) = CHROME_GUARD.get() {
+        if let Some(guard) = guard.lock().unwrap().as_ref() {
+            guard.flush();
+        }
+    }
+}
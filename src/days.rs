@@ -0,0 +1,79 @@
+use std::io::{self, Read};
+use std::path::PathBuf;
+use std::process::Command;
+
+use utils::results::{self, InputKind, PartResult};
+
+#[derive(Debug, Clone)]
+pub struct Day {
+    pub name: String,
+    pub number: u32,
+}
+
+pub fn workspace_root() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+}
+
+/// Lists all `crates/dayN` crates, ordered by day number.
+pub fn discover() -> io::Result<Vec<Day>> {
+    let mut days = std::fs::read_dir(workspace_root().join("crates"))?
+        .filter_map(|entry| entry.ok())
+        .filter_map(|entry| {
+            let name = entry.file_name().into_string().ok()?;
+            let number = name.strip_prefix("day")?.parse().ok()?;
+            Some(Day { name, number })
+        })
+        .collect::<Vec<_>>();
+    days.sort_by_key(|day| day.number);
+    Ok(days)
+}
+
+impl Day {
+    fn state_path(&self, input: InputKind, extension: &str) -> PathBuf {
+        workspace_root()
+            .join("target")
+            .join("aoc")
+            .join(format!("{}-{}.{}", self.name, input, extension))
+    }
+
+    pub fn results_path(&self, input: InputKind) -> PathBuf {
+        self.state_path(input, "results")
+    }
+
+    pub fn log_path(&self, input: InputKind) -> PathBuf {
+        self.state_path(input, "log")
+    }
+
+    /// Results and combined output of the last run on this input, if any.
+    pub fn last_run(&self, input: InputKind) -> (Vec<PartResult>, String) {
+        (
+            results::load(self.results_path(input)),
+            std::fs::read_to_string(self.log_path(input)).unwrap_or_default(),
+        )
+    }
+
+    /// Runs the day in release mode with only the checks for `input` enabled.
+    /// Stdout and stderr share one pipe so solution output stays in order.
+    pub fn run(&self, input: InputKind) -> io::Result<(Vec<PartResult>, String)> {
+        let results_path = self.results_path(input);
+        std::fs::create_dir_all(results_path.parent().unwrap())?;
+        let _ = std::fs::remove_file(&results_path);
+
+        let (mut reader, writer) = io::pipe()?;
+        let cargo = std::env::var_os("CARGO").unwrap_or_else(|| "cargo".into());
+        let mut child = Command::new(cargo)
+            .args(["run", "--quiet", "--release", "-p", &self.name])
+            .current_dir(workspace_root())
+            .env(results::RESULTS_ENV, &results_path)
+            .env(results::INPUT_ENV, input.to_string())
+            .stdout(writer.try_clone()?)
+            .stderr(writer)
+            .spawn()?;
+        let mut log = String::new();
+        reader.read_to_string(&mut log)?;
+        child.wait()?;
+
+        std::fs::write(self.log_path(input), &log)?;
+        Ok((results::load(&results_path), log))
+    }
+}
@@ -0,0 +1,62 @@
+use std::io;
+use std::path::PathBuf;
+
+use utils::crypt::{self, InputKey};
+
+use crate::days;
+
+fn key() -> io::Result<InputKey> {
+    InputKey::from_env()
+        .ok_or_else(|| io::Error::other(format!("{} is not set", crypt::KEY_ENV)))?
+        .map_err(|e| io::Error::other(e.to_string()))
+}
+
+pub fn keygen() -> io::Result<()> {
+    println!("{}", InputKey::generate().to_base64());
+    Ok(())
+}
+
+/// The given files, or every day's real input if none are given.
+fn input_paths(args: &[String]) -> io::Result<Vec<PathBuf>> {
+    if !args.is_empty() {
+        return Ok(args.iter().map(PathBuf::from).collect());
+    }
+    Ok(days::discover()?
+        .iter()
+        .map(|day| {
+            days::workspace_root()
+                .join("crates")
+                .join(&day.name)
+                .join("src/input.txt")
+        })
+        .filter(|path| path.exists())
+        .collect())
+}
+
+pub fn encrypt(args: &[String]) -> io::Result<()> {
+    let key = key()?;
+    for path in input_paths(args)? {
+        let text = std::fs::read_to_string(&path)?;
+        if text.trim().is_empty() || crypt::is_encrypted(&text) {
+            continue;
+        }
+        std::fs::write(&path, crypt::encrypt(&text, &key))?;
+        eprintln!("Encrypted {}", path.display());
+    }
+    Ok(())
+}
+
+pub fn decrypt(args: &[String]) -> io::Result<()> {
+    let key = key()?;
+    for path in input_paths(args)? {
+        let text = std::fs::read_to_string(&path)?;
+        if !crypt::is_encrypted(&text) {
+            continue;
+        }
+        let plaintext = crypt::decrypt(&text, &key)
+            .map_err(|e| io::Error::other(format!("{}: {}", path.display(), e)))?;
+        std::fs::write(&path, plaintext)?;
+        eprintln!("Decrypted {}", path.display());
+    }
+    Ok(())
+}
@@ -1,3 +1,25 @@
-fn main() {
-    println!("Hello, world!");
+mod days;
+mod inputs;
+mod tui;
+
+fn usage() -> ! {
+    eprintln!("Usage: aoc <command>");
+    eprintln!();
+    eprintln!("Commands:");
+    eprintln!("  tui                Interactive dashboard for all days");
+    eprintln!("  encrypt [files]    Encrypt real inputs in place using AOC_INPUT_KEY");
+    eprintln!("  decrypt [files]    Decrypt real inputs in place using AOC_INPUT_KEY");
+    eprintln!("  keygen             Print a new random key for AOC_INPUT_KEY");
+    std::process::exit(2);
+}
+
+fn main() -> std::io::Result<()> {
+    let args = std::env::args().skip(1).collect::<Vec<_>>();
+    match args.first().map(String::as_str) {
+        Some("tui") => tui::run(),
+        Some("encrypt") => inputs::encrypt(&args[1..]),
+        Some("decrypt") => inputs::decrypt(&args[1..]),
+        Some("keygen") => inputs::keygen(),
+        _ => usage(),
+    }
 }
@@ -0,0 +1,301 @@
+use std::io;
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::time::Duration;
+
+use ratatui::crossterm::event::{self, Event, KeyCode, KeyEventKind};
+use ratatui::layout::{Constraint, Layout};
+use ratatui::style::{Color, Modifier, Style, Stylize};
+use ratatui::text::{Line, Text};
+use ratatui::widgets::{Block, Cell, Paragraph, Row, Table, TableState};
+use ratatui::{DefaultTerminal, Frame};
+use utils::results::{self, InputKind, PartResult, Status};
+
+use crate::days::{self, Day};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum OutputView {
+    All,
+    Part(u8),
+}
+
+impl OutputView {
+    fn next(&self) -> Self {
+        match self {
+            Self::All => Self::Part(1),
+            Self::Part(1) => Self::Part(2),
+            Self::Part(_) => Self::All,
+        }
+    }
+}
+
+struct DayState {
+    day: Day,
+    results: Vec<PartResult>,
+    log: String,
+}
+
+struct RunFinished {
+    index: usize,
+    input: InputKind,
+    outcome: io::Result<(Vec<PartResult>, String)>,
+}
+
+struct App {
+    days: Vec<DayState>,
+    table: TableState,
+    input: InputKind,
+    view: OutputView,
+    scroll: u16,
+    running: Option<usize>,
+    tx: Sender<RunFinished>,
+    rx: Receiver<RunFinished>,
+    quit: bool,
+}
+
+pub fn run() -> io::Result<()> {
+    let days = days::discover()?;
+    let mut app = App::new(days);
+    let mut terminal = ratatui::init();
+    let result = app.event_loop(&mut terminal);
+    ratatui::restore();
+    result
+}
+
+impl App {
+    fn new(days: Vec<Day>) -> Self {
+        let (tx, rx) = mpsc::channel();
+        let mut app = Self {
+            days: days
+                .into_iter()
+                .map(|day| DayState {
+                    day,
+                    results: vec![],
+                    log: String::new(),
+                })
+                .collect(),
+            table: TableState::default().with_selected(0),
+            input: InputKind::Example,
+            view: OutputView::All,
+            scroll: 0,
+            running: None,
+            tx,
+            rx,
+            quit: false,
+        };
+        app.load_last_runs();
+        app
+    }
+
+    fn load_last_runs(&mut self) {
+        for state in &mut self.days {
+            (state.results, state.log) = state.day.last_run(self.input);
+        }
+    }
+
+    fn selected(&self) -> Option<usize> {
+        self.table.selected().filter(|&i| i < self.days.len())
+    }
+
+    fn event_loop(&mut self, terminal: &mut DefaultTerminal) -> io::Result<()> {
+        while !self.quit {
+            terminal.draw(|frame| self.draw(frame))?;
+            if let Ok(finished) = self.rx.try_recv() {
+                self.finish_run(finished);
+            }
+            if event::poll(Duration::from_millis(100))?
+                && let Event::Key(key) = event::read()?
+                && key.kind == KeyEventKind::Press
+            {
+                self.handle_key(key.code);
+            }
+        }
+        Ok(())
+    }
+
+    fn handle_key(&mut self, code: KeyCode) {
+        match code {
+            KeyCode::Char('q') | KeyCode::Esc => self.quit = true,
+            KeyCode::Down | KeyCode::Char('j') => {
+                self.table.select_next();
+                self.scroll = 0;
+            }
+            KeyCode::Up | KeyCode::Char('k') => {
+                self.table.select_previous();
+                self.scroll = 0;
+            }
+            KeyCode::Enter | KeyCode::Char('r') => self.start_run(),
+            KeyCode::Char('e') => {
+                self.input = self.input.toggle();
+                self.scroll = 0;
+                self.load_last_runs();
+            }
+            KeyCode::Tab => {
+                self.view = self.view.next();
+                self.scroll = 0;
+            }
+            KeyCode::PageDown => self.scroll_down(10),
+            KeyCode::PageUp => self.scroll = self.scroll.saturating_sub(10),
+            KeyCode::Char('J') => self.scroll_down(1),
+            KeyCode::Char('K') => self.scroll = self.scroll.saturating_sub(1),
+            KeyCode::Home => self.scroll = 0,
+            _ => {}
+        }
+    }
+
+    fn start_run(&mut self) {
+        let Some(index) = self.selected() else {
+            return;
+        };
+        if self.running.is_some() {
+            return;
+        }
+        self.running = Some(index);
+        let day = self.days[index].day.clone();
+        let input = self.input;
+        let tx = self.tx.clone();
+        std::thread::spawn(move || {
+            let outcome = day.run(input);
+            let _ = tx.send(RunFinished {
+                index,
+                input,
+                outcome,
+            });
+        });
+    }
+
+    fn finish_run(&mut self, finished: RunFinished) {
+        self.running = None;
+        if finished.input != self.input {
+            return;
+        }
+        let state = &mut self.days[finished.index];
+        match finished.outcome {
+            Ok((results, log)) => {
+                state.results = results;
+                state.log = log;
+            }
+            Err(e) => {
+                state.results = vec![];
+                state.log = format!("Failed to run {}: {}", state.day.name, e);
+            }
+        }
+        self.scroll = 0;
+    }
+
+    fn draw(&mut self, frame: &mut Frame) {
+        let [table_area, output_area, help_area] = Layout::vertical([
+            Constraint::Length(self.days.len() as u16 + 3),
+            Constraint::Min(5),
+            Constraint::Length(1),
+        ])
+        .areas(frame.area());
+
+        let table = self.days_table();
+        frame.render_stateful_widget(table, table_area, &mut self.table);
+        frame.render_widget(self.output_pane(), output_area);
+        frame.render_widget(
+            Line::from(
+                " ↑/↓ select  r run  e example/real  tab output part  PgUp/PgDn scroll  q quit",
+            )
+            .dark_gray(),
+            help_area,
+        );
+    }
+
+    fn days_table(&self) -> Table<'static> {
+        let header = [
+            "Day", "Part 1", "Time", "Answer", "Part 2", "Time", "Answer",
+        ]
+        .into_iter()
+        .map(|title| Cell::from(title).bold())
+        .collect::<Row>();
+        let rows = self.days.iter().enumerate().map(|(i, state)| {
+            let mut cells = vec![Cell::from(state.day.name.clone())];
+            for part in [1, 2] {
+                let result = state.results.iter().find(|r| r.part == part);
+                if self.running == Some(i) {
+                    cells.push(Cell::from("running…").yellow());
+                    cells.push(Cell::from(""));
+                    cells.push(Cell::from(""));
+                } else if let Some(result) = result {
+                    let status = match result.status {
+                        Status::Passed => Cell::from("✔ passed").green(),
+                        Status::Failed => Cell::from("✘ failed").red(),
+                    };
+                    cells.push(status);
+                    cells.push(Cell::from(format!("{:.2?}", result.elapsed)));
+                    cells.push(Cell::from(result.answer.clone()));
+                } else {
+                    cells.push(Cell::from("-").dark_gray());
+                    cells.push(Cell::from(""));
+                    cells.push(Cell::from(""));
+                }
+            }
+            Row::new(cells)
+        });
+        Table::new(
+            rows,
+            [
+                Constraint::Length(6),
+                Constraint::Length(9),
+                Constraint::Length(10),
+                Constraint::Fill(1),
+                Constraint::Length(9),
+                Constraint::Length(10),
+                Constraint::Fill(1),
+            ],
+        )
+        .header(header)
+        .row_highlight_style(Style::default().add_modifier(Modifier::REVERSED))
+        .block(Block::bordered().title(format!(" Advent of Code 2025 ({} input) ", self.input)))
+    }
+
+    /// Scrolls the output down, stopping at its last row.
+    fn scroll_down(&mut self, rows: u16) {
+        self.scroll = self.scroll.saturating_add(rows).min(self.max_scroll());
+    }
+
+    fn max_scroll(&self) -> u16 {
+        let rows = self
+            .selected()
+            .map_or(0, |i| filter_output(&self.days[i].log, self.view).count());
+        u16::try_from(rows.saturating_sub(1)).unwrap_or(u16::MAX)
+    }
+
+    fn output_pane(&self) -> Paragraph<'_> {
+        let Some(state) = self.selected().map(|i| &self.days[i]) else {
+            return Paragraph::new("");
+        };
+        let title = match self.view {
+            OutputView::All => format!(" {} output ", state.day.name),
+            OutputView::Part(part) => format!(" {} part {} output ", state.day.name, part),
+        };
+        let text = filter_output(&state.log, self.view)
+            .map(|line| {
+                if results::parse_marker(line).is_some() {
+                    Line::from(line).fg(Color::Cyan)
+                } else {
+                    Line::from(line)
+                }
+            })
+            .collect::<Text>();
+        Paragraph::new(text)
+            .scroll((self.scroll.min(self.max_scroll()), 0))
+            .block(Block::bordered().title(title))
+    }
+}
+
+/// Keeps only the log lines that belong to the viewed part, using the
+/// markers the harness prints before each part runs.
+fn filter_output(log: &str, view: OutputView) -> impl Iterator<Item = &str> {
+    let mut current = None;
+    log.lines().filter(move |line| {
+        if let Some((part, _)) = results::parse_marker(line) {
+            current = Some(part);
+        }
+        match view {
+            OutputView::All => true,
+            OutputView::Part(part) => current == Some(part),
+        }
+    })
+}